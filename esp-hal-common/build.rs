@@ -41,6 +41,13 @@ fn main() {
         println!("cargo:rustc-cfg=has_systimer");
     }
 
+    // Configuration symbol for the kind of DMA controller
+    if esp32c3 || esp32s3 {
+        println!("cargo:rustc-cfg=gdma");
+    } else {
+        println!("cargo:rustc-cfg=pdma");
+    }
+
     // Configuration symbol for the USB_SERIAL_JTAG peripheral
     if esp32c3 || esp32s3 {
         println!("cargo:rustc-cfg=has_usb_serial_jtag");
@@ -0,0 +1,366 @@
+//! General Direct Memory Access (GDMA)
+//!
+//! The GDMA controller of the ESP32-C3 and ESP32-S3 offers channels which can
+//! be used with any DMA capable peripheral.
+
+use crate::{
+    dma::{private::RegisterAccess, *},
+    system::{Peripheral, PeripheralClockControl},
+};
+
+macro_rules! impl_channel {
+    ($num:literal) => {
+        paste::paste! {
+            /// Register access of the GDMA channel
+            #[doc(hidden)]
+            pub struct [<Channel $num>] {}
+
+            impl RegisterAccess for [<Channel $num>] {
+                fn init_channel() {
+                    // nothing special to be done here
+                }
+
+                fn set_out_burstmode(burst_mode: bool) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<out_conf0_ch $num>].modify(|_, w| {
+                        w.[<out_data_burst_en_ch $num>]()
+                            .bit(burst_mode)
+                            .[<outdscr_burst_en_ch $num>]()
+                            .bit(burst_mode)
+                    });
+                }
+
+                fn set_out_priority(priority: DmaPriority) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<out_pri_ch $num>]
+                        .write(|w| unsafe { w.[<tx_pri_ch $num>]().bits(priority as u8) });
+                }
+
+                fn clear_out_interrupts() {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    #[cfg(esp32c3)]
+                    dma.[<int_clr_ch $num>].write(|w| {
+                        w.[<out_eof_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<out_dscr_err_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<out_done_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<out_total_eof_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<outfifo_ovf_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<outfifo_udf_ch $num _int_clr>]()
+                            .set_bit()
+                    });
+
+                    #[cfg(esp32s3)]
+                    dma.[<out_int_clr_ch $num>].write(|w| {
+                        w.out_eof_int_clr()
+                            .set_bit()
+                            .out_dscr_err_int_clr()
+                            .set_bit()
+                            .out_done_int_clr()
+                            .set_bit()
+                            .out_total_eof_int_clr()
+                            .set_bit()
+                            .outfifo_ovf_l1_int_clr()
+                            .set_bit()
+                            .outfifo_udf_l1_int_clr()
+                            .set_bit()
+                    });
+                }
+
+                fn reset_out() {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<out_conf0_ch $num>]
+                        .modify(|_, w| w.[<out_rst_ch $num>]().set_bit());
+                    dma.[<out_conf0_ch $num>]
+                        .modify(|_, w| w.[<out_rst_ch $num>]().clear_bit());
+                }
+
+                fn set_out_descriptors(address: u32) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    // Only the lower 20 bits of the address are used
+                    dma.[<out_link_ch $num>].modify(|_, w| unsafe {
+                        w.[<outlink_addr_ch $num>]().bits(address & 0xfffff)
+                    });
+                }
+
+                fn has_out_descriptor_error() -> bool {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    #[cfg(esp32c3)]
+                    let error = dma.[<int_raw_ch $num>]
+                        .read()
+                        .[<out_dscr_err_ch $num _int_raw>]()
+                        .bit_is_set();
+
+                    #[cfg(esp32s3)]
+                    let error = dma.[<out_int_raw_ch $num>]
+                        .read()
+                        .out_dscr_err_int_raw()
+                        .bit_is_set();
+
+                    error
+                }
+
+                fn set_out_peripheral(peripheral: u8) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<out_peri_sel_ch $num>]
+                        .modify(|_, w| unsafe { w.[<peri_out_sel_ch $num>]().bits(peripheral) });
+                }
+
+                fn start_out() {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<out_link_ch $num>]
+                        .modify(|_, w| w.[<outlink_start_ch $num>]().set_bit());
+                }
+
+                fn is_out_done() -> bool {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    #[cfg(esp32c3)]
+                    let done = dma.[<int_raw_ch $num>]
+                        .read()
+                        .[<out_total_eof_ch $num _int_raw>]()
+                        .bit_is_set();
+
+                    #[cfg(esp32s3)]
+                    let done = dma.[<out_int_raw_ch $num>]
+                        .read()
+                        .out_total_eof_int_raw()
+                        .bit_is_set();
+
+                    done
+                }
+
+                fn set_in_burstmode(burst_mode: bool) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<in_conf0_ch $num>].modify(|_, w| {
+                        w.[<in_data_burst_en_ch $num>]()
+                            .bit(burst_mode)
+                            .[<indscr_burst_en_ch $num>]()
+                            .bit(burst_mode)
+                    });
+                }
+
+                fn set_in_priority(priority: DmaPriority) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<in_pri_ch $num>]
+                        .write(|w| unsafe { w.[<rx_pri_ch $num>]().bits(priority as u8) });
+                }
+
+                fn clear_in_interrupts() {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    #[cfg(esp32c3)]
+                    dma.[<int_clr_ch $num>].write(|w| {
+                        w.[<in_suc_eof_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<in_err_eof_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<in_dscr_err_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<in_dscr_empty_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<in_done_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<infifo_ovf_ch $num _int_clr>]()
+                            .set_bit()
+                            .[<infifo_udf_ch $num _int_clr>]()
+                            .set_bit()
+                    });
+
+                    #[cfg(esp32s3)]
+                    dma.[<in_int_clr_ch $num>].write(|w| {
+                        w.in_suc_eof_int_clr()
+                            .set_bit()
+                            .in_err_eof_int_clr()
+                            .set_bit()
+                            .in_dscr_err_int_clr()
+                            .set_bit()
+                            .in_dscr_empty_int_clr()
+                            .set_bit()
+                            .in_done_int_clr()
+                            .set_bit()
+                            .infifo_ovf_l1_int_clr()
+                            .set_bit()
+                            .infifo_udf_l1_int_clr()
+                            .set_bit()
+                    });
+                }
+
+                fn reset_in() {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<in_conf0_ch $num>]
+                        .modify(|_, w| w.[<in_rst_ch $num>]().set_bit());
+                    dma.[<in_conf0_ch $num>]
+                        .modify(|_, w| w.[<in_rst_ch $num>]().clear_bit());
+                }
+
+                fn set_in_descriptors(address: u32) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    // Only the lower 20 bits of the address are used
+                    dma.[<in_link_ch $num>].modify(|_, w| unsafe {
+                        w.[<inlink_addr_ch $num>]().bits(address & 0xfffff)
+                    });
+                }
+
+                fn has_in_descriptor_error() -> bool {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    #[cfg(esp32c3)]
+                    let error = dma.[<int_raw_ch $num>]
+                        .read()
+                        .[<in_dscr_err_ch $num _int_raw>]()
+                        .bit_is_set();
+
+                    #[cfg(esp32s3)]
+                    let error = dma.[<in_int_raw_ch $num>]
+                        .read()
+                        .in_dscr_err_int_raw()
+                        .bit_is_set();
+
+                    error
+                }
+
+                fn set_in_peripheral(peripheral: u8) {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<in_peri_sel_ch $num>]
+                        .modify(|_, w| unsafe { w.[<peri_in_sel_ch $num>]().bits(peripheral) });
+                }
+
+                fn start_in() {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    dma.[<in_link_ch $num>]
+                        .modify(|_, w| w.[<inlink_start_ch $num>]().set_bit());
+                }
+
+                fn is_in_done() -> bool {
+                    let dma = unsafe { &*crate::pac::DMA::PTR };
+
+                    #[cfg(esp32c3)]
+                    let done = dma.[<int_raw_ch $num>]
+                        .read()
+                        .[<in_suc_eof_ch $num _int_raw>]()
+                        .bit_is_set();
+
+                    #[cfg(esp32s3)]
+                    let done = dma.[<in_int_raw_ch $num>]
+                        .read()
+                        .in_suc_eof_int_raw()
+                        .bit_is_set();
+
+                    done
+                }
+            }
+
+            /// Marks the peripherals the GDMA channel can be used with, which
+            /// are all of them
+            pub struct [<SuitablePeripheral $num>] {}
+
+            impl PeripheralMarker for [<SuitablePeripheral $num>] {}
+            impl SpiPeripheral for [<SuitablePeripheral $num>] {}
+            impl Spi2Peripheral for [<SuitablePeripheral $num>] {}
+            #[cfg(esp32s3)]
+            impl Spi3Peripheral for [<SuitablePeripheral $num>] {}
+            impl I2sPeripheral for [<SuitablePeripheral $num>] {}
+            impl I2s0Peripheral for [<SuitablePeripheral $num>] {}
+            #[cfg(esp32s3)]
+            impl I2s1Peripheral for [<SuitablePeripheral $num>] {}
+
+            /// Creates the TX and RX halves of the GDMA channel
+            pub struct [<ChannelCreator $num>] {
+                _private: (),
+            }
+
+            impl [<ChannelCreator $num>] {
+                /// Configure the channel for use
+                ///
+                /// The descriptors need to hold 3 words for each 4092 bytes of
+                /// the largest buffer to be transferred.
+                pub fn configure<'a>(
+                    self,
+                    burst_mode: bool,
+                    tx_descriptors: &'a mut [u32],
+                    rx_descriptors: &'a mut [u32],
+                    priority: DmaPriority,
+                ) -> Channel<
+                    ChannelTx<'a, [<Channel $num>]>,
+                    ChannelRx<'a, [<Channel $num>]>,
+                    [<SuitablePeripheral $num>],
+                > {
+                    [<Channel $num>]::init_channel();
+
+                    Channel::new(
+                        ChannelTx::new(tx_descriptors, burst_mode, priority),
+                        ChannelRx::new(rx_descriptors, burst_mode, priority),
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_channel!(0);
+impl_channel!(1);
+impl_channel!(2);
+#[cfg(esp32s3)]
+impl_channel!(3);
+#[cfg(esp32s3)]
+impl_channel!(4);
+
+/// GDMA peripheral
+///
+/// Offers the available DMA channels.
+pub struct Gdma {
+    _inner: crate::pac::DMA,
+    pub channel0: ChannelCreator0,
+    pub channel1: ChannelCreator1,
+    pub channel2: ChannelCreator2,
+    #[cfg(esp32s3)]
+    pub channel3: ChannelCreator3,
+    #[cfg(esp32s3)]
+    pub channel4: ChannelCreator4,
+}
+
+impl Gdma {
+    /// Create a new GDMA instance
+    /// This will enable the peripheral but the peripheral won't get
+    /// automatically disabled when this gets dropped.
+    pub fn new(
+        dma: crate::pac::DMA,
+        peripheral_clock_control: &mut PeripheralClockControl,
+    ) -> Self {
+        peripheral_clock_control.enable(Peripheral::Gdma);
+
+        dma.misc_conf.modify(|_, w| w.ahbm_rst_inter().set_bit());
+        dma.misc_conf.modify(|_, w| w.ahbm_rst_inter().clear_bit());
+        dma.misc_conf.modify(|_, w| w.clk_en().set_bit());
+
+        Gdma {
+            _inner: dma,
+            channel0: ChannelCreator0 { _private: () },
+            channel1: ChannelCreator1 { _private: () },
+            channel2: ChannelCreator2 { _private: () },
+            #[cfg(esp32s3)]
+            channel3: ChannelCreator3 { _private: () },
+            #[cfg(esp32s3)]
+            channel4: ChannelCreator4 { _private: () },
+        }
+    }
+}
@@ -0,0 +1,410 @@
+//! Direct Memory Access
+//!
+//! DMA transfers are described by linked lists of descriptors, each pointing
+//! to a chunk of (at most 4092 bytes of) the buffer. The descriptors are placed
+//! in memory provided by the user, 3 words are needed for each chunk.
+//!
+//! The ESP32-C3 and ESP32-S3 have a general DMA controller (GDMA) whose
+//! channels can be connected to any DMA capable peripheral, see `gdma`. On the
+//! ESP32 and ESP32-S2 the SPI and I2S peripherals have their own DMA engines,
+//! see `pdma`.
+//!
+//! Example
+//! ```no_run
+//! static mut TX_DESCRIPTORS: [u32; 8 * 3] = [0u32; 8 * 3];
+//! static mut RX_DESCRIPTORS: [u32; 8 * 3] = [0u32; 8 * 3];
+//!
+//! let dma = Gdma::new(peripherals.DMA, &mut system.peripheral_clock_control);
+//! let channel = dma.channel0.configure(
+//!     false,
+//!     unsafe { &mut TX_DESCRIPTORS },
+//!     unsafe { &mut RX_DESCRIPTORS },
+//!     DmaPriority::Priority0,
+//! );
+//! ```
+
+use core::{
+    marker::PhantomData,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use self::private::{RegisterAccess, RxPrivate, TxPrivate};
+
+#[cfg(gdma)]
+pub mod gdma;
+#[cfg(pdma)]
+pub mod pdma;
+
+/// Maximum number of bytes a single descriptor can point to (word aligned)
+const CHUNK_SIZE: usize = 4092;
+
+/// Descriptor flag marking the last descriptor of a transfer
+const DESCRIPTOR_SUC_EOF: u32 = 1 << 30;
+/// Descriptor flag handing the descriptor over to the DMA engine
+const DESCRIPTOR_OWNER_DMA: u32 = 1 << 31;
+
+/// DMA errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DmaError {
+    /// The buffer is not suitably aligned for the transfer
+    InvalidAlignment,
+    /// Not enough descriptors to describe the whole buffer
+    OutOfDescriptors,
+    /// The descriptor memory is not a multiple of 3 words
+    InvalidDescriptorSize,
+    /// The buffer to transfer is empty
+    EmptyBuffer,
+    /// The DMA engine rejected a descriptor, e.g. because the buffer is not
+    /// located in RAM
+    DescriptorError,
+}
+
+/// DMA channel priorities
+///
+/// Only the GDMA controller supports priorities, they are ignored otherwise.
+#[derive(Clone, Copy)]
+pub enum DmaPriority {
+    Priority0 = 0,
+    Priority1 = 1,
+    Priority2 = 2,
+    Priority3 = 3,
+    Priority4 = 4,
+    Priority5 = 5,
+    Priority6 = 6,
+    Priority7 = 7,
+    Priority8 = 8,
+    Priority9 = 9,
+}
+
+/// DMA capable peripherals
+///
+/// The values are used to select the peripheral of a GDMA channel and need to
+/// match the TRM.
+#[derive(Clone, Copy)]
+pub enum DmaPeripheral {
+    Spi2 = 0,
+    #[cfg(not(esp32c3))]
+    Spi3 = 1,
+    I2s0 = 3,
+    #[cfg(any(esp32, esp32s3))]
+    I2s1 = 4,
+}
+
+/// Marks peripherals which DMA channels can be used with
+pub trait PeripheralMarker {}
+
+/// Marks channels as usable for SPI
+pub trait SpiPeripheral: PeripheralMarker {}
+
+/// Marks channels as usable for SPI2
+pub trait Spi2Peripheral: SpiPeripheral {}
+
+/// Marks channels as usable for SPI3
+#[cfg(not(esp32c3))]
+pub trait Spi3Peripheral: SpiPeripheral {}
+
+/// Marks channels as usable for I2S
+pub trait I2sPeripheral: PeripheralMarker {}
+
+/// Marks channels as usable for I2S0
+pub trait I2s0Peripheral: I2sPeripheral {}
+
+/// Marks channels as usable for I2S1
+#[cfg(any(esp32, esp32s3))]
+pub trait I2s1Peripheral: I2sPeripheral {}
+
+/// The transmitting half of a DMA channel
+pub trait Tx: TxPrivate {}
+
+/// The receiving half of a DMA channel
+pub trait Rx: RxPrivate {}
+
+/// The functions here are not meant to be used outside the HAL
+pub(crate) mod private {
+    use super::{DmaError, DmaPeripheral, DmaPriority};
+
+    pub trait TxPrivate {
+        /// Sets up the descriptors for `len` bytes starting at `data` and
+        /// starts the transfer to the peripheral
+        fn prepare_transfer(
+            &mut self,
+            peripheral: DmaPeripheral,
+            data: *const u8,
+            len: usize,
+        ) -> Result<(), DmaError>;
+
+        /// Returns `true` once all data has been handed to the peripheral
+        fn is_done(&self) -> bool;
+    }
+
+    pub trait RxPrivate {
+        /// Sets up the descriptors for `len` bytes starting at `data` and
+        /// starts the transfer from the peripheral
+        fn prepare_transfer(
+            &mut self,
+            peripheral: DmaPeripheral,
+            data: *mut u8,
+            len: usize,
+        ) -> Result<(), DmaError>;
+
+        /// Returns `true` once the peripheral signaled the end of the
+        /// received data
+        fn is_done(&self) -> bool;
+    }
+
+    /// Access to the registers of a single DMA channel
+    pub trait RegisterAccess {
+        fn init_channel();
+        fn set_out_burstmode(burst_mode: bool);
+        fn set_out_priority(priority: DmaPriority);
+        fn clear_out_interrupts();
+        fn reset_out();
+        fn set_out_descriptors(address: u32);
+        fn has_out_descriptor_error() -> bool;
+        fn set_out_peripheral(peripheral: u8);
+        fn start_out();
+        fn is_out_done() -> bool;
+        fn set_in_burstmode(burst_mode: bool);
+        fn set_in_priority(priority: DmaPriority);
+        fn clear_in_interrupts();
+        fn reset_in();
+        fn set_in_descriptors(address: u32);
+        fn has_in_descriptor_error() -> bool;
+        fn set_in_peripheral(peripheral: u8);
+        fn start_in();
+        fn is_in_done() -> bool;
+    }
+}
+
+/// The transmitting half of a DMA channel, using the descriptors it was
+/// configured with
+pub struct ChannelTx<'a, C>
+where
+    C: RegisterAccess,
+{
+    descriptors: &'a mut [u32],
+    _phantom: PhantomData<C>,
+}
+
+impl<'a, C> ChannelTx<'a, C>
+where
+    C: RegisterAccess,
+{
+    fn new(descriptors: &'a mut [u32], burst_mode: bool, priority: DmaPriority) -> Self {
+        C::set_out_burstmode(burst_mode);
+        C::set_out_priority(priority);
+
+        ChannelTx {
+            descriptors,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, C> Tx for ChannelTx<'a, C> where C: RegisterAccess {}
+
+impl<'a, C> TxPrivate for ChannelTx<'a, C>
+where
+    C: RegisterAccess,
+{
+    fn prepare_transfer(
+        &mut self,
+        peripheral: DmaPeripheral,
+        data: *const u8,
+        len: usize,
+    ) -> Result<(), DmaError> {
+        // Outgoing descriptors point to the data to send, the last one marks
+        // the end of the transfer
+        fill_descriptors(self.descriptors, data, len, true)?;
+
+        C::clear_out_interrupts();
+        C::reset_out();
+        C::set_out_descriptors(self.descriptors.as_ptr() as u32);
+        C::set_out_peripheral(peripheral as u8);
+        C::start_out();
+
+        if C::has_out_descriptor_error() {
+            return Err(DmaError::DescriptorError);
+        }
+
+        Ok(())
+    }
+
+    fn is_done(&self) -> bool {
+        C::is_out_done()
+    }
+}
+
+/// The receiving half of a DMA channel, using the descriptors it was
+/// configured with
+pub struct ChannelRx<'a, C>
+where
+    C: RegisterAccess,
+{
+    descriptors: &'a mut [u32],
+    _phantom: PhantomData<C>,
+}
+
+impl<'a, C> ChannelRx<'a, C>
+where
+    C: RegisterAccess,
+{
+    fn new(descriptors: &'a mut [u32], burst_mode: bool, priority: DmaPriority) -> Self {
+        C::set_in_burstmode(burst_mode);
+        C::set_in_priority(priority);
+
+        ChannelRx {
+            descriptors,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, C> Rx for ChannelRx<'a, C> where C: RegisterAccess {}
+
+impl<'a, C> RxPrivate for ChannelRx<'a, C>
+where
+    C: RegisterAccess,
+{
+    fn prepare_transfer(
+        &mut self,
+        peripheral: DmaPeripheral,
+        data: *mut u8,
+        len: usize,
+    ) -> Result<(), DmaError> {
+        // The DMA engines of the ESP32 and ESP32-S2 can only receive whole
+        // words into word aligned buffers
+        #[cfg(pdma)]
+        if data as usize % 4 != 0 || len % 4 != 0 {
+            return Err(DmaError::InvalidAlignment);
+        }
+
+        // Incoming descriptors describe free space, the DMA engine fills in
+        // the received length and the end of the transfer
+        fill_descriptors(self.descriptors, data, len, false)?;
+
+        C::clear_in_interrupts();
+        C::reset_in();
+        C::set_in_descriptors(self.descriptors.as_ptr() as u32);
+        C::set_in_peripheral(peripheral as u8);
+        C::start_in();
+
+        if C::has_in_descriptor_error() {
+            return Err(DmaError::DescriptorError);
+        }
+
+        Ok(())
+    }
+
+    fn is_done(&self) -> bool {
+        C::is_in_done()
+    }
+}
+
+/// A DMA channel, consisting of a transmitting and a receiving half
+///
+/// `P` marks the peripherals the channel can be used with.
+pub struct Channel<TX, RX, P>
+where
+    TX: Tx,
+    RX: Rx,
+    P: PeripheralMarker,
+{
+    pub(crate) tx: TX,
+    pub(crate) rx: RX,
+    _phantom: PhantomData<P>,
+}
+
+impl<TX, RX, P> Channel<TX, RX, P>
+where
+    TX: Tx,
+    RX: Rx,
+    P: PeripheralMarker,
+{
+    fn new(tx: TX, rx: RX) -> Self {
+        Channel {
+            tx,
+            rx,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// An in-progress DMA transfer
+///
+/// The transfer owns the buffer and the peripheral involved, they are handed
+/// back once it is done. Dropping the transfer waits for it to finish.
+pub trait DmaTransfer<B, T>: Drop {
+    /// Check if the transfer is finished
+    fn is_done(&self) -> bool;
+
+    /// Wait for the transfer to finish and return the buffer and the
+    /// peripheral
+    fn wait(self) -> (B, T);
+}
+
+/// Builds a linked list of descriptors for `len` bytes starting at `data`
+///
+/// For outgoing transfers the descriptors are filled with the length of the
+/// data and the last one is marked as the end of the transfer.
+fn fill_descriptors(
+    descriptors: &mut [u32],
+    data: *const u8,
+    len: usize,
+    outgoing: bool,
+) -> Result<(), DmaError> {
+    if descriptors.len() % 3 != 0 {
+        return Err(DmaError::InvalidDescriptorSize);
+    }
+
+    if len == 0 {
+        return Err(DmaError::EmptyBuffer);
+    }
+
+    if descriptors.len() / 3 < (len + CHUNK_SIZE - 1) / CHUNK_SIZE {
+        return Err(DmaError::OutOfDescriptors);
+    }
+
+    descriptors.fill(0);
+    compiler_fence(Ordering::SeqCst);
+
+    let base = descriptors.as_ptr() as u32;
+    let mut processed = 0;
+    for (index, descriptor) in descriptors.chunks_exact_mut(3).enumerate() {
+        let chunk_size = usize::min(CHUNK_SIZE, len - processed);
+        let last = processed + chunk_size >= len;
+
+        descriptor[0] = if outgoing {
+            descriptor_flags(chunk_size, chunk_size, last)
+        } else {
+            descriptor_flags(chunk_size, 0, false)
+        };
+        descriptor[1] = data as u32 + processed as u32;
+        descriptor[2] = if last {
+            0
+        } else {
+            base + ((index + 1) * 3 * core::mem::size_of::<u32>()) as u32
+        };
+
+        processed += chunk_size;
+        if last {
+            break;
+        }
+    }
+
+    compiler_fence(Ordering::SeqCst);
+
+    Ok(())
+}
+
+/// Composes the first word of a descriptor which is handed to the DMA engine
+///
+/// `size` is the size of the buffer, `length` the number of valid bytes in it.
+fn descriptor_flags(size: usize, length: usize, suc_eof: bool) -> u32 {
+    let mut flags = (size as u32 & 0xfff) | (length as u32 & 0xfff) << 12 | DESCRIPTOR_OWNER_DMA;
+    if suc_eof {
+        flags |= DESCRIPTOR_SUC_EOF;
+    }
+
+    flags
+}
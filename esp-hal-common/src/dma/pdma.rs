@@ -0,0 +1,432 @@
+//! Peripheral Direct Memory Access (PDMA)
+//!
+//! On the ESP32 and ESP32-S2 the SPI and I2S peripherals have their own DMA
+//! engines, so each channel can only be used with its peripheral.
+
+use crate::{
+    dma::{private::RegisterAccess, *},
+    system::{Peripheral, PeripheralClockControl},
+};
+
+macro_rules! impl_spi_channel {
+    ($num:literal) => {
+        paste::paste! {
+            /// Register access of the SPI DMA channel
+            #[doc(hidden)]
+            pub struct [<Spi $num DmaChannel>] {}
+
+            impl RegisterAccess for [<Spi $num DmaChannel>] {
+                fn init_channel() {
+                    // (only) on the ESP32 the SPI peripherals share two DMA channels,
+                    // which need to be assigned via DPORT
+                    #[cfg(esp32)]
+                    {
+                        let dport = unsafe { &*crate::pac::DPORT::PTR };
+
+                        match $num {
+                            2 => dport
+                                .spi_dma_chan_sel
+                                .modify(|_, w| unsafe { w.spi2_dma_chan_sel().bits(1) }),
+                            3 => dport
+                                .spi_dma_chan_sel
+                                .modify(|_, w| unsafe { w.spi3_dma_chan_sel().bits(2) }),
+                            _ => unreachable!(),
+                        }
+                    }
+                }
+
+                fn set_out_burstmode(burst_mode: bool) {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_conf.modify(|_, w| {
+                        w.outdscr_burst_en()
+                            .bit(burst_mode)
+                            .out_data_burst_en()
+                            .bit(burst_mode)
+                    });
+                }
+
+                fn set_out_priority(_priority: DmaPriority) {}
+
+                fn clear_out_interrupts() {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_int_clr.write(|w| {
+                        w.out_done_int_clr()
+                            .set_bit()
+                            .out_eof_int_clr()
+                            .set_bit()
+                            .out_total_eof_int_clr()
+                            .set_bit()
+                            .outlink_dscr_error_int_clr()
+                            .set_bit()
+                    });
+                }
+
+                fn reset_out() {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_conf.modify(|_, w| w.out_rst().set_bit());
+                    spi.dma_conf.modify(|_, w| w.out_rst().clear_bit());
+                }
+
+                fn set_out_descriptors(address: u32) {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    // Only the lower 20 bits of the address are used
+                    spi.dma_out_link
+                        .modify(|_, w| unsafe { w.outlink_addr().bits(address & 0xfffff) });
+                }
+
+                fn has_out_descriptor_error() -> bool {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_int_raw
+                        .read()
+                        .outlink_dscr_error_int_raw()
+                        .bit_is_set()
+                }
+
+                fn set_out_peripheral(_peripheral: u8) {
+                    // the channel is tied to its peripheral
+                }
+
+                fn start_out() {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_out_link.modify(|_, w| w.outlink_start().set_bit());
+                }
+
+                fn is_out_done() -> bool {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_int_raw.read().out_total_eof_int_raw().bit_is_set()
+                }
+
+                fn set_in_burstmode(burst_mode: bool) {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_conf
+                        .modify(|_, w| w.indscr_burst_en().bit(burst_mode));
+                }
+
+                fn set_in_priority(_priority: DmaPriority) {}
+
+                fn clear_in_interrupts() {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_int_clr.write(|w| {
+                        w.in_done_int_clr()
+                            .set_bit()
+                            .in_err_eof_int_clr()
+                            .set_bit()
+                            .in_suc_eof_int_clr()
+                            .set_bit()
+                            .inlink_dscr_error_int_clr()
+                            .set_bit()
+                    });
+                }
+
+                fn reset_in() {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_conf.modify(|_, w| w.in_rst().set_bit());
+                    spi.dma_conf.modify(|_, w| w.in_rst().clear_bit());
+                }
+
+                fn set_in_descriptors(address: u32) {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    // Only the lower 20 bits of the address are used
+                    spi.dma_in_link
+                        .modify(|_, w| unsafe { w.inlink_addr().bits(address & 0xfffff) });
+                }
+
+                fn has_in_descriptor_error() -> bool {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_int_raw
+                        .read()
+                        .inlink_dscr_error_int_raw()
+                        .bit_is_set()
+                }
+
+                fn set_in_peripheral(_peripheral: u8) {
+                    // the channel is tied to its peripheral
+                }
+
+                fn start_in() {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_in_link.modify(|_, w| w.inlink_start().set_bit());
+                }
+
+                fn is_in_done() -> bool {
+                    let spi = unsafe { &*crate::pac::[<SPI $num>]::PTR };
+
+                    spi.dma_int_raw.read().in_suc_eof_int_raw().bit_is_set()
+                }
+            }
+
+            /// Marks the peripheral the SPI DMA channel can be used with
+            pub struct [<Spi $num DmaSuitablePeripheral>] {}
+
+            impl PeripheralMarker for [<Spi $num DmaSuitablePeripheral>] {}
+            impl SpiPeripheral for [<Spi $num DmaSuitablePeripheral>] {}
+            impl [<Spi $num Peripheral>] for [<Spi $num DmaSuitablePeripheral>] {}
+
+            /// Creates the TX and RX halves of the SPI DMA channel
+            pub struct [<Spi $num DmaChannelCreator>] {
+                _private: (),
+            }
+
+            impl [<Spi $num DmaChannelCreator>] {
+                /// Configure the channel for use
+                ///
+                /// The descriptors need to hold 3 words for each 4092 bytes of
+                /// the largest buffer to be transferred. The priority is
+                /// ignored.
+                pub fn configure<'a>(
+                    self,
+                    burst_mode: bool,
+                    tx_descriptors: &'a mut [u32],
+                    rx_descriptors: &'a mut [u32],
+                    priority: DmaPriority,
+                ) -> Channel<
+                    ChannelTx<'a, [<Spi $num DmaChannel>]>,
+                    ChannelRx<'a, [<Spi $num DmaChannel>]>,
+                    [<Spi $num DmaSuitablePeripheral>],
+                > {
+                    [<Spi $num DmaChannel>]::init_channel();
+
+                    Channel::new(
+                        ChannelTx::new(tx_descriptors, burst_mode, priority),
+                        ChannelRx::new(rx_descriptors, burst_mode, priority),
+                    )
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_i2s_channel {
+    ($num:literal) => {
+        paste::paste! {
+            /// Register access of the I2S DMA channel
+            #[doc(hidden)]
+            pub struct [<I2s $num DmaChannel>] {}
+
+            impl RegisterAccess for [<I2s $num DmaChannel>] {
+                fn init_channel() {
+                    // nothing special to be done here
+                }
+
+                fn set_out_burstmode(burst_mode: bool) {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.lc_conf.modify(|_, w| {
+                        w.outdscr_burst_en()
+                            .bit(burst_mode)
+                            .out_data_burst_en()
+                            .bit(burst_mode)
+                    });
+                }
+
+                fn set_out_priority(_priority: DmaPriority) {}
+
+                fn clear_out_interrupts() {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.int_clr.write(|w| {
+                        w.out_done_int_clr()
+                            .set_bit()
+                            .out_eof_int_clr()
+                            .set_bit()
+                            .out_total_eof_int_clr()
+                            .set_bit()
+                            .out_dscr_err_int_clr()
+                            .set_bit()
+                    });
+                }
+
+                fn reset_out() {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.lc_conf.modify(|_, w| w.out_rst().set_bit());
+                    i2s.lc_conf.modify(|_, w| w.out_rst().clear_bit());
+                }
+
+                fn set_out_descriptors(address: u32) {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    // Only the lower 20 bits of the address are used
+                    i2s.out_link
+                        .modify(|_, w| unsafe { w.outlink_addr().bits(address & 0xfffff) });
+                }
+
+                fn has_out_descriptor_error() -> bool {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.int_raw.read().out_dscr_err_int_raw().bit_is_set()
+                }
+
+                fn set_out_peripheral(_peripheral: u8) {
+                    // the channel is tied to its peripheral
+                }
+
+                fn start_out() {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.out_link.modify(|_, w| w.outlink_start().set_bit());
+                }
+
+                fn is_out_done() -> bool {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.int_raw.read().out_total_eof_int_raw().bit_is_set()
+                }
+
+                fn set_in_burstmode(burst_mode: bool) {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.lc_conf
+                        .modify(|_, w| w.indscr_burst_en().bit(burst_mode));
+                }
+
+                fn set_in_priority(_priority: DmaPriority) {}
+
+                fn clear_in_interrupts() {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.int_clr.write(|w| {
+                        w.in_done_int_clr()
+                            .set_bit()
+                            .in_err_eof_int_clr()
+                            .set_bit()
+                            .in_suc_eof_int_clr()
+                            .set_bit()
+                            .in_dscr_err_int_clr()
+                            .set_bit()
+                    });
+                }
+
+                fn reset_in() {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.lc_conf.modify(|_, w| w.in_rst().set_bit());
+                    i2s.lc_conf.modify(|_, w| w.in_rst().clear_bit());
+                }
+
+                fn set_in_descriptors(address: u32) {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    // Only the lower 20 bits of the address are used
+                    i2s.in_link
+                        .modify(|_, w| unsafe { w.inlink_addr().bits(address & 0xfffff) });
+                }
+
+                fn has_in_descriptor_error() -> bool {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.int_raw.read().in_dscr_err_int_raw().bit_is_set()
+                }
+
+                fn set_in_peripheral(_peripheral: u8) {
+                    // the channel is tied to its peripheral
+                }
+
+                fn start_in() {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.in_link.modify(|_, w| w.inlink_start().set_bit());
+                }
+
+                fn is_in_done() -> bool {
+                    let i2s = unsafe { &*crate::pac::[<I2S $num>]::PTR };
+
+                    i2s.int_raw.read().in_suc_eof_int_raw().bit_is_set()
+                }
+            }
+
+            /// Marks the peripheral the I2S DMA channel can be used with
+            pub struct [<I2s $num DmaSuitablePeripheral>] {}
+
+            impl PeripheralMarker for [<I2s $num DmaSuitablePeripheral>] {}
+            impl I2sPeripheral for [<I2s $num DmaSuitablePeripheral>] {}
+            impl [<I2s $num Peripheral>] for [<I2s $num DmaSuitablePeripheral>] {}
+
+            /// Creates the TX and RX halves of the I2S DMA channel
+            pub struct [<I2s $num DmaChannelCreator>] {
+                _private: (),
+            }
+
+            impl [<I2s $num DmaChannelCreator>] {
+                /// Configure the channel for use
+                ///
+                /// The descriptors need to hold 3 words for each 4092 bytes of
+                /// the largest buffer to be transferred. The priority is
+                /// ignored.
+                ///
+                /// The clock of the I2S peripheral needs to be enabled before
+                /// the channel can be configured.
+                pub fn configure<'a>(
+                    self,
+                    burst_mode: bool,
+                    tx_descriptors: &'a mut [u32],
+                    rx_descriptors: &'a mut [u32],
+                    priority: DmaPriority,
+                ) -> Channel<
+                    ChannelTx<'a, [<I2s $num DmaChannel>]>,
+                    ChannelRx<'a, [<I2s $num DmaChannel>]>,
+                    [<I2s $num DmaSuitablePeripheral>],
+                > {
+                    [<I2s $num DmaChannel>]::init_channel();
+
+                    Channel::new(
+                        ChannelTx::new(tx_descriptors, burst_mode, priority),
+                        ChannelRx::new(rx_descriptors, burst_mode, priority),
+                    )
+                }
+            }
+        }
+    };
+}
+
+impl_spi_channel!(2);
+impl_spi_channel!(3);
+impl_i2s_channel!(0);
+#[cfg(esp32)]
+impl_i2s_channel!(1);
+
+/// DMA peripheral
+///
+/// Offers the DMA channels of the SPI and I2S peripherals.
+pub struct Dma {
+    _inner: crate::system::Dma,
+    pub spi2channel: Spi2DmaChannelCreator,
+    pub spi3channel: Spi3DmaChannelCreator,
+    pub i2s0channel: I2s0DmaChannelCreator,
+    #[cfg(esp32)]
+    pub i2s1channel: I2s1DmaChannelCreator,
+}
+
+impl Dma {
+    /// Create a new DMA instance
+    /// This will enable the DMA of the SPI peripherals but it won't get
+    /// automatically disabled when this gets dropped.
+    pub fn new(
+        dma: crate::system::Dma,
+        peripheral_clock_control: &mut PeripheralClockControl,
+    ) -> Self {
+        peripheral_clock_control.enable(Peripheral::Dma);
+
+        Dma {
+            _inner: dma,
+            spi2channel: Spi2DmaChannelCreator { _private: () },
+            spi3channel: Spi3DmaChannelCreator { _private: () },
+            i2s0channel: I2s0DmaChannelCreator { _private: () },
+            #[cfg(esp32)]
+            i2s1channel: I2s1DmaChannelCreator { _private: () },
+        }
+    }
+}
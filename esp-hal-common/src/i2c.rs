@@ -2,10 +2,12 @@
 //!
 //! Supports multiple I2C peripheral instances
 
-use core::convert::TryInto;
+use core::{convert::TryInto, iter::Peekable};
 
 use fugit::HertzU32;
 
+#[cfg(esp32)]
+use crate::types::OutputSignalType;
 use crate::{
     clock::Clocks,
     gpio::{InputPin, OutputPin},
@@ -22,25 +24,56 @@ cfg_if::cfg_if! {
     }
 }
 
+/// The size of the TX and RX FIFO buffers
+const I2C_FIFO_SIZE: usize = 32;
+
 /// I2C-specific transmission errors
 #[derive(Debug)]
 pub enum Error {
     ExceedingFifo,
-    AckCheckFailed,
+    AckCheckFailed(AckFailure),
     TimeOut,
     ArbitrationLost,
     ExecIncomplete,
     CommandNrExceeded,
+    /// A slave stretched the clock for longer than the configured limit
+    ClockStretchTimeout,
+    /// The address exceeds the range of the addressing mode
+    InvalidAddress,
+    /// The buffer passed to [`I2C::poll_transfer`] doesn't match the number of
+    /// bytes requested
+    InvalidBufferLength,
+}
+
+/// Part of a transmission which was not acknowledged by the slave
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckFailure {
+    /// The address was not acknowledged, i.e. no slave with that address is
+    /// present
+    Address,
+    /// A data byte was not acknowledged
+    Data,
+    /// It could not be determined which byte was not acknowledged
+    Unknown,
 }
 
 #[cfg(feature = "eh1")]
 impl embedded_hal_1::i2c::Error for Error {
     fn kind(&self) -> embedded_hal_1::i2c::ErrorKind {
-        use embedded_hal_1::i2c::ErrorKind;
+        use embedded_hal_1::i2c::{ErrorKind, NoAcknowledgeSource};
 
         match self {
             Self::ExceedingFifo => ErrorKind::Overrun,
             Self::ArbitrationLost => ErrorKind::ArbitrationLoss,
+            Self::AckCheckFailed(AckFailure::Address) => {
+                ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)
+            }
+            Self::AckCheckFailed(AckFailure::Data) => {
+                ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)
+            }
+            Self::AckCheckFailed(AckFailure::Unknown) => {
+                ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)
+            }
             _ => ErrorKind::Other,
         }
     }
@@ -51,12 +84,65 @@ impl embedded_hal_1::i2c::Error for Error {
 pub enum SetupError {
     InvalidClkConfig,
     PeripheralDisabled,
+    InvalidAddress,
+    InvalidTimeoutConfig,
+}
+
+/// I2C master configuration
+#[derive(Debug, Copy, Clone)]
+pub struct I2cConfig {
+    /// Bus frequency
+    pub frequency: HertzU32,
+    /// Number of SCL periods the bus may remain unchanged during a
+    /// transmission before it is aborted with [`Error::TimeOut`]. `None`
+    /// disables the timeout, except on the ESP32 where the longest possible
+    /// timeout is used instead.
+    pub timeout: Option<u32>,
+    /// Number of SCL periods a slave may stretch the clock before the
+    /// transmission is aborted with [`Error::ClockStretchTimeout`]. `None`
+    /// selects the longest possible limit. Only the C3 and S3 support this,
+    /// the other chips cover clock stretching with `timeout`.
+    pub stretch_limit: Option<u32>,
+}
+
+impl I2cConfig {
+    pub fn frequency(mut self, frequency: HertzU32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set the bus timeout in SCL periods, `None` disables it (see
+    /// [`I2cConfig::timeout`])
+    pub fn timeout(mut self, timeout: Option<u32>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the clock stretching limit in SCL periods, `None` selects the
+    /// longest possible limit (see [`I2cConfig::stretch_limit`])
+    pub fn stretch_limit(mut self, stretch_limit: Option<u32>) -> Self {
+        self.stretch_limit = stretch_limit;
+        self
+    }
+}
+
+impl Default for I2cConfig {
+    fn default() -> I2cConfig {
+        I2cConfig {
+            frequency: HertzU32::kHz(100),
+            timeout: None,
+            stretch_limit: None,
+        }
+    }
 }
 
 /// A generic I2C Command
 enum Command {
     Start,
     Stop,
+    /// Pauses the transmission (holding SCL low) until the command list is
+    /// refilled and the transmission is restarted.
+    End,
     Write {
         /// This bit is to set an expected ACK value for the transmitter.
         ack_exp: Ack,
@@ -81,29 +167,30 @@ impl From<Command> for u16 {
         let opcode = match c {
             Command::Start => Opcode::RStart,
             Command::Stop => Opcode::Stop,
+            Command::End => Opcode::End,
             Command::Write { .. } => Opcode::Write,
             Command::Read { .. } => Opcode::Read,
         };
 
         let length = match c {
-            Command::Start | Command::Stop => 0,
+            Command::Start | Command::Stop | Command::End => 0,
             Command::Write { length: l, .. } | Command::Read { length: l, .. } => l,
         };
 
         let ack_exp = match c {
-            Command::Start | Command::Stop | Command::Read { .. } => Ack::Nack,
+            Command::Start | Command::Stop | Command::End | Command::Read { .. } => Ack::Nack,
             Command::Write { ack_exp: exp, .. } => exp,
         };
 
         let ack_check_en = match c {
-            Command::Start | Command::Stop | Command::Read { .. } => false,
+            Command::Start | Command::Stop | Command::End | Command::Read { .. } => false,
             Command::Write {
                 ack_check_en: en, ..
             } => en,
         };
 
         let ack_value = match c {
-            Command::Start | Command::Stop | Command::Write { .. } => Ack::Nack,
+            Command::Start | Command::Stop | Command::End | Command::Write { .. } => Ack::Nack,
             Command::Read { ack_value: ack, .. } => ack,
         };
 
@@ -133,6 +220,7 @@ impl From<Command> for u16 {
     }
 }
 
+#[derive(Eq, PartialEq, Copy, Clone)]
 enum OperationType {
     Write = 0,
     Read  = 1,
@@ -150,6 +238,7 @@ enum Opcode {
     Write  = 1,
     Read   = 3,
     Stop   = 2,
+    End    = 4,
 }
 
 #[cfg(any(esp32, esp32s2))]
@@ -158,11 +247,20 @@ enum Opcode {
     Write  = 1,
     Read   = 2,
     Stop   = 3,
+    End    = 4,
 }
 
 /// I2C peripheral container (I2C)
 pub struct I2C<T> {
     peripheral: T,
+    // Number of bytes requested by the last `start_*` call
+    read_length: usize,
+    // The ESP32 can't clear the bus in hardware, so it needs to know which
+    // pins to drive manually
+    #[cfg(esp32)]
+    sda_pin: u8,
+    #[cfg(esp32)]
+    scl_pin: u8,
 }
 
 impl<T> embedded_hal::blocking::i2c::Read for I2C<T>
@@ -172,7 +270,7 @@ where
     type Error = Error;
 
     fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.peripheral.master_read(address, buffer)
+        self.peripheral.master_read(address.into(), buffer)
     }
 }
 
@@ -183,7 +281,7 @@ where
     type Error = Error;
 
     fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.peripheral.master_write(addr, bytes)
+        self.peripheral.master_write(addr.into(), bytes)
     }
 }
 
@@ -199,7 +297,8 @@ where
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.peripheral.master_write_read(address, bytes, buffer)
+        self.peripheral
+            .master_write_read(address.into(), bytes, buffer)
     }
 }
 
@@ -208,60 +307,131 @@ impl<T> embedded_hal_1::i2c::ErrorType for I2C<T> {
     type Error = Error;
 }
 
+/// Implemented for both 7-bit ([`SevenBitAddress`]) and 10-bit
+/// ([`TenBitAddress`]) addressing
+///
+/// [`SevenBitAddress`]: embedded_hal_1::i2c::SevenBitAddress
+/// [`TenBitAddress`]: embedded_hal_1::i2c::TenBitAddress
 #[cfg(feature = "eh1")]
-impl<T> embedded_hal_1::i2c::I2c for I2C<T>
+impl<T, A> embedded_hal_1::i2c::I2c<A> for I2C<T>
 where
     T: Instance,
+    A: embedded_hal_1::i2c::AddressMode + Into<SlaveAddress>,
 {
-    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.peripheral.master_read(address, buffer)
+    fn read(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.peripheral.master_read(address.into(), buffer)
     }
 
-    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.peripheral.master_write(address, bytes)
+    fn write(&mut self, address: A, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.peripheral.master_write(address.into(), bytes)
     }
 
-    fn write_iter<B>(&mut self, _address: u8, _bytes: B) -> Result<(), Self::Error>
+    fn write_iter<B>(&mut self, address: A, bytes: B) -> Result<(), Self::Error>
     where
         B: IntoIterator<Item = u8>,
     {
-        todo!()
+        self.peripheral.write_operation(
+            address.into(),
+            &mut bytes.into_iter().peekable(),
+            true,
+            true,
+        )
     }
 
     fn write_read(
         &mut self,
-        address: u8,
+        address: A,
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.peripheral.master_write_read(address, bytes, buffer)
+        self.peripheral
+            .master_write_read(address.into(), bytes, buffer)
     }
 
     fn write_iter_read<B>(
         &mut self,
-        _address: u8,
-        _bytes: B,
-        _buffer: &mut [u8],
+        address: A,
+        bytes: B,
+        buffer: &mut [u8],
     ) -> Result<(), Self::Error>
     where
         B: IntoIterator<Item = u8>,
     {
-        todo!()
+        let address = address.into();
+
+        self.peripheral
+            .write_operation(address, &mut bytes.into_iter().peekable(), true, false)?;
+        self.peripheral
+            .read_operation(address, buffer, true, true, true, false)
     }
 
     fn transaction<'a>(
         &mut self,
-        _address: u8,
-        _operations: &mut [embedded_hal_1::i2c::Operation<'a>],
+        address: A,
+        operations: &mut [embedded_hal_1::i2c::Operation<'a>],
     ) -> Result<(), Self::Error> {
-        todo!()
+        self.transaction_iter(address, operations.iter_mut().map(reborrow_operation))
     }
 
-    fn transaction_iter<'a, O>(&mut self, _address: u8, _operations: O) -> Result<(), Self::Error>
+    fn transaction_iter<'a, O>(&mut self, address: A, operations: O) -> Result<(), Self::Error>
     where
         O: IntoIterator<Item = embedded_hal_1::i2c::Operation<'a>>,
     {
-        todo!()
+        use embedded_hal_1::i2c::Operation;
+
+        fn operation_type(op: &Operation<'_>) -> OperationType {
+            match op {
+                Operation::Write(_) => OperationType::Write,
+                Operation::Read(_) => OperationType::Read,
+            }
+        }
+
+        let address = address.into();
+        let mut last_op: Option<OperationType> = None;
+        let mut op_iter = operations.into_iter().peekable();
+
+        while let Some(op) = op_iter.next() {
+            let next_op = op_iter.peek().map(operation_type);
+            let this_op = operation_type(&op);
+
+            // A (repeated) START and the address are only sent if the direction
+            // changes, adjacent operations of the same type are merged. A STOP
+            // is only sent after the last operation.
+            match op {
+                Operation::Write(bytes) => self.peripheral.write_operation(
+                    address,
+                    &mut bytes.iter().copied().peekable(),
+                    last_op != Some(OperationType::Write),
+                    next_op.is_none(),
+                )?,
+                Operation::Read(buffer) => self.peripheral.read_operation(
+                    address,
+                    buffer,
+                    last_op != Some(OperationType::Read),
+                    last_op == Some(OperationType::Write),
+                    next_op.is_none(),
+                    next_op == Some(OperationType::Read),
+                )?,
+            }
+
+            last_op = Some(this_op);
+        }
+
+        Ok(())
+    }
+}
+
+/// Reborrows an operation from a slice of operations, so it can be passed on
+/// by value
+#[cfg(feature = "eh1")]
+fn reborrow_operation<'a, 'b>(
+    op: &'b mut embedded_hal_1::i2c::Operation<'a>,
+) -> embedded_hal_1::i2c::Operation<'b> {
+    use embedded_hal_1::i2c::Operation;
+
+    match op {
+        Operation::Write(bytes) => Operation::Write(*bytes),
+        Operation::Read(buffer) => Operation::Read(&mut **buffer),
     }
 }
 
@@ -273,40 +443,682 @@ where
     /// This will enable the peripheral but the peripheral won't get
     /// automatically disabled when this gets dropped.
     pub fn new<SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
+        i2c: T,
+        sda: SDA,
+        scl: SCL,
+        frequency: HertzU32,
+        peripheral_clock_control: &mut PeripheralClockControl,
+        clocks: &Clocks,
+    ) -> Result<Self, SetupError> {
+        Self::new_with_config(
+            i2c,
+            sda,
+            scl,
+            I2cConfig::default().frequency(frequency),
+            peripheral_clock_control,
+            clocks,
+        )
+    }
+
+    /// Create a new I2C instance with the given configuration
+    /// This will enable the peripheral but the peripheral won't get
+    /// automatically disabled when this gets dropped.
+    pub fn new_with_config<SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
         i2c: T,
         mut sda: SDA,
         mut scl: SCL,
-        frequency: HertzU32,
+        config: I2cConfig,
         peripheral_clock_control: &mut PeripheralClockControl,
         clocks: &Clocks,
     ) -> Result<Self, SetupError> {
         enable_peripheral(&i2c, peripheral_clock_control);
 
-        let mut i2c = I2C { peripheral: i2c };
+        let mut i2c = I2C {
+            peripheral: i2c,
+            read_length: 0,
+            #[cfg(esp32)]
+            sda_pin: sda.number(),
+            #[cfg(esp32)]
+            scl_pin: scl.number(),
+        };
+
+        connect_pins(&i2c.peripheral, &mut sda, &mut scl);
+
+        i2c.peripheral.setup(&config, clocks)?;
+
+        Ok(i2c)
+    }
+
+    /// Attempt to free a bus on which a slave holds SDA low
+    ///
+    /// A slave which lost track of a transmission, e.g. after an
+    /// [`Error::ArbitrationLost`] or [`Error::TimeOut`], might keep SDA low
+    /// while waiting for more clock pulses. Up to 9 SCL pulses are generated
+    /// to let it complete its byte, followed by a STOP condition. Afterwards
+    /// the FSM, the FIFOs and the command list of the controller are reset.
+    /// The enabled interrupts are kept.
+    pub fn recover_bus(&mut self) {
+        #[cfg(esp32)]
+        self.clear_bus_manually();
+
+        #[cfg(not(esp32))]
+        self.peripheral.clear_bus();
+
+        // The reset disables all interrupts, restore the ones that were enabled
+        let int_ena = self.peripheral.register_block().int_ena.read().bits();
+        self.peripheral.reset();
+        self.peripheral
+            .register_block()
+            .int_ena
+            .write(|w| unsafe { w.bits(int_ena) });
+    }
+
+    /// Clears the bus by temporarily driving SCL and SDA as GPIOs
+    #[cfg(esp32)]
+    fn clear_bus_manually(&mut self) {
+        let gpio = unsafe { &*crate::pac::GPIO::PTR };
+        let route = |pin: u8, signal: OutputSignal| {
+            gpio.func_out_sel_cfg[pin as usize]
+                .modify(|_, w| unsafe { w.out_sel().bits(signal as OutputSignalType) });
+        };
+
+        // Release both lines before taking them over from the peripheral
+        set_pin_level(self.sda_pin, true);
+        set_pin_level(self.scl_pin, true);
+        route(self.sda_pin, OutputSignal::GPIO);
+        route(self.scl_pin, OutputSignal::GPIO);
 
-        sda.set_to_open_drain_output()
-            .enable_input(true)
-            .internal_pull_up(true)
-            .connect_peripheral_to_output(OutputSignal::I2CEXT0_SDA)
-            .connect_input_to_peripheral(InputSignal::I2CEXT0_SDA);
+        // Clock until the slave releases SDA
+        for _ in 0..9 {
+            if is_pin_high(self.sda_pin) {
+                break;
+            }
 
-        scl.set_to_open_drain_output()
-            .enable_input(true)
-            .internal_pull_up(true)
-            .connect_peripheral_to_output(OutputSignal::I2CEXT0_SCL)
-            .connect_input_to_peripheral(InputSignal::I2CEXT0_SCL);
+            set_pin_level(self.scl_pin, false);
+            bus_recovery_delay();
+            set_pin_level(self.scl_pin, true);
+            bus_recovery_delay();
+        }
 
-        i2c.peripheral.setup(frequency, clocks)?;
+        // STOP condition, SDA goes high while SCL is high
+        set_pin_level(self.scl_pin, false);
+        bus_recovery_delay();
+        set_pin_level(self.sda_pin, false);
+        bus_recovery_delay();
+        set_pin_level(self.scl_pin, true);
+        bus_recovery_delay();
+        set_pin_level(self.sda_pin, true);
+        bus_recovery_delay();
+
+        route(self.sda_pin, self.peripheral.sda_output_signal());
+        route(self.scl_pin, self.peripheral.scl_output_signal());
+    }
+
+    /// Start writing `bytes` to the slave with the address `address` without
+    /// waiting for the transfer to finish
+    ///
+    /// The address and the data have to fit into the FIFO at once, otherwise
+    /// [`Error::ExceedingFifo`] is returned. Use [`I2C::poll_transfer`] to
+    /// check for completion.
+    pub fn start_write<A: Into<SlaveAddress>>(
+        &mut self,
+        address: A,
+        bytes: &[u8],
+    ) -> Result<(), Error> {
+        self.peripheral.reset_fifo();
+        self.peripheral.reset_command_list();
+
+        let mut cmd_iterator = self.peripheral.register_block().comd.iter();
+        let done = self.peripheral.add_write_operation(
+            address.into(),
+            &mut bytes.iter().copied().peekable(),
+            &mut cmd_iterator,
+            true,
+        )?;
+
+        if !done {
+            self.peripheral.reset_fifo();
+            self.peripheral.reset_command_list();
+            return Err(Error::ExceedingFifo);
+        }
+
+        add_cmd(&mut cmd_iterator, Command::Stop)?;
+        self.read_length = 0;
+        self.peripheral.start_transmission();
+
+        Ok(())
+    }
+
+    /// Start reading `length` bytes from the slave with the address `address`
+    /// without waiting for the transfer to finish
+    ///
+    /// At most 32 bytes can be read at once, otherwise
+    /// [`Error::ExceedingFifo`] is returned. The received bytes are handed
+    /// out by [`I2C::poll_transfer`] once the transfer is done.
+    pub fn start_read<A: Into<SlaveAddress>>(
+        &mut self,
+        address: A,
+        length: usize,
+    ) -> Result<(), Error> {
+        if length == 0 {
+            // Without any data this is just a check if the slave is present,
+            // which doesn't need the direction of the transfer
+            return self.start_write(address, &[]);
+        }
+
+        if length > I2C_FIFO_SIZE {
+            return Err(Error::ExceedingFifo);
+        }
+
+        self.peripheral.reset_fifo();
+        self.peripheral.reset_command_list();
+
+        let mut cmd_iterator = self.peripheral.register_block().comd.iter();
+        self.peripheral.add_read_operation(
+            address.into(),
+            length,
+            &mut cmd_iterator,
+            true,
+            false,
+            false,
+        )?;
+        add_cmd(&mut cmd_iterator, Command::Stop)?;
+        self.read_length = length;
+        self.peripheral.start_transmission();
+
+        Ok(())
+    }
+
+    /// Start writing `bytes` and then reading `length` bytes after a repeated
+    /// START without waiting for the transfer to finish
+    ///
+    /// Both the write (including the addresses) and the read have to fit into
+    /// the FIFOs at once, otherwise [`Error::ExceedingFifo`] is returned. The
+    /// received bytes are handed out by [`I2C::poll_transfer`] once the
+    /// transfer is done.
+    pub fn start_write_read<A: Into<SlaveAddress>>(
+        &mut self,
+        address: A,
+        bytes: &[u8],
+        length: usize,
+    ) -> Result<(), Error> {
+        let address = address.into();
+
+        if length == 0 {
+            return self.start_write(address, bytes);
+        }
+
+        // The address for the read is loaded into the TX FIFO as well. The
+        // slave has already been addressed by the write, so a 10-bit address
+        // only needs its first byte after the repeated START.
+        let address_length = match address {
+            SlaveAddress::SevenBit(_) => 2,
+            SlaveAddress::TenBit(_) => 3,
+        };
+        if bytes.len() + address_length > I2C_FIFO_SIZE || length > I2C_FIFO_SIZE {
+            return Err(Error::ExceedingFifo);
+        }
+
+        // START, address, data, RSTART, address, one or two READs and STOP
+        let commands = 6 + !bytes.is_empty() as usize + (length > 1) as usize;
+        if commands > self.peripheral.register_block().comd.len() {
+            return Err(Error::CommandNrExceeded);
+        }
+
+        self.peripheral.reset_fifo();
+        self.peripheral.reset_command_list();
+
+        let mut cmd_iterator = self.peripheral.register_block().comd.iter();
+        self.peripheral.add_write_operation(
+            address,
+            &mut bytes.iter().copied().peekable(),
+            &mut cmd_iterator,
+            true,
+        )?;
+        self.peripheral.add_read_operation(
+            address,
+            length,
+            &mut cmd_iterator,
+            true,
+            true,
+            false,
+        )?;
+        add_cmd(&mut cmd_iterator, Command::Stop)?;
+        self.read_length = length;
+        self.peripheral.start_transmission();
+
+        Ok(())
+    }
+
+    /// Check if the transfer started by one of the `start_*` functions is done
+    ///
+    /// Returns `nb::Error::WouldBlock` as long as the transfer is in progress.
+    /// Once it is done, the bytes read are copied into `buffer`, which must be
+    /// as long as the number of bytes requested (or empty for a write),
+    /// otherwise [`Error::InvalidBufferLength`] is returned.
+    pub fn poll_transfer(&mut self, buffer: &mut [u8]) -> nb::Result<(), Error> {
+        if buffer.len() != self.read_length {
+            return Err(nb::Error::Other(Error::InvalidBufferLength));
+        }
+
+        self.peripheral.check_transmission()?;
+
+        for byte in buffer.iter_mut() {
+            *byte = read_fifo(self.peripheral.register_block());
+        }
+
+        Ok(())
+    }
+
+    /// Listen for the end of a transmission
+    pub fn listen_trans_complete(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.trans_complete_int_ena().set_bit());
+    }
+
+    /// Stop listening for the end of a transmission
+    pub fn unlisten_trans_complete(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.trans_complete_int_ena().clear_bit());
+    }
+
+    /// Checks if the end of a transmission was signaled
+    pub fn trans_complete_interrupt_set(&self) -> bool {
+        self.peripheral
+            .register_block()
+            .int_raw
+            .read()
+            .trans_complete_int_raw()
+            .bit_is_set()
+    }
+
+    /// Reset the end of transmission interrupt
+    pub fn reset_trans_complete_interrupt(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_clr
+            .write(|w| w.trans_complete_int_clr().set_bit());
+    }
+
+    /// Listen for a missing ACK from the slave
+    pub fn listen_nack(&mut self) {
+        // The ESP32 calls this an ACK error
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                self.peripheral
+                    .register_block()
+                    .int_ena
+                    .modify(|_, w| w.ack_err_int_ena().set_bit());
+            } else {
+                self.peripheral
+                    .register_block()
+                    .int_ena
+                    .modify(|_, w| w.nack_int_ena().set_bit());
+            }
+        }
+    }
+
+    /// Stop listening for a missing ACK from the slave
+    pub fn unlisten_nack(&mut self) {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                self.peripheral
+                    .register_block()
+                    .int_ena
+                    .modify(|_, w| w.ack_err_int_ena().clear_bit());
+            } else {
+                self.peripheral
+                    .register_block()
+                    .int_ena
+                    .modify(|_, w| w.nack_int_ena().clear_bit());
+            }
+        }
+    }
+
+    /// Checks if a missing ACK from the slave was signaled
+    pub fn nack_interrupt_set(&self) -> bool {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                self.peripheral
+                    .register_block()
+                    .int_raw
+                    .read()
+                    .ack_err_int_raw()
+                    .bit_is_set()
+            } else {
+                self.peripheral
+                    .register_block()
+                    .int_raw
+                    .read()
+                    .nack_int_raw()
+                    .bit_is_set()
+            }
+        }
+    }
+
+    /// Reset the missing ACK interrupt
+    pub fn reset_nack_interrupt(&mut self) {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                self.peripheral
+                    .register_block()
+                    .int_clr
+                    .write(|w| w.ack_err_int_clr().set_bit());
+            } else {
+                self.peripheral
+                    .register_block()
+                    .int_clr
+                    .write(|w| w.nack_int_clr().set_bit());
+            }
+        }
+    }
+
+    /// Listen for a lost arbitration
+    pub fn listen_arbitration_lost(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.arbitration_lost_int_ena().set_bit());
+    }
+
+    /// Stop listening for a lost arbitration
+    pub fn unlisten_arbitration_lost(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.arbitration_lost_int_ena().clear_bit());
+    }
+
+    /// Checks if a lost arbitration was signaled
+    pub fn arbitration_lost_interrupt_set(&self) -> bool {
+        self.peripheral
+            .register_block()
+            .int_raw
+            .read()
+            .arbitration_lost_int_raw()
+            .bit_is_set()
+    }
+
+    /// Reset the lost arbitration interrupt
+    pub fn reset_arbitration_lost_interrupt(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_clr
+            .write(|w| w.arbitration_lost_int_clr().set_bit());
+    }
+
+    /// Listen for a timeout on the bus
+    pub fn listen_time_out(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.time_out_int_ena().set_bit());
+    }
+
+    /// Stop listening for a timeout on the bus
+    pub fn unlisten_time_out(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.time_out_int_ena().clear_bit());
+    }
+
+    /// Checks if a timeout on the bus was signaled
+    pub fn time_out_interrupt_set(&self) -> bool {
+        self.peripheral
+            .register_block()
+            .int_raw
+            .read()
+            .time_out_int_raw()
+            .bit_is_set()
+    }
+
+    /// Reset the bus timeout interrupt
+    pub fn reset_time_out_interrupt(&mut self) {
+        self.peripheral
+            .register_block()
+            .int_clr
+            .write(|w| w.time_out_int_clr().set_bit());
+    }
+
+    /// Return the raw interface to the underlying peripheral
+    pub fn free(self) -> T {
+        self.peripheral
+    }
+}
+
+#[cfg(esp32)]
+fn set_pin_level(pin: u8, high: bool) {
+    use crate::gpio::{Bank0GpioRegisterAccess, Bank1GpioRegisterAccess, BankGpioRegisterAccess};
+
+    match (pin < 32, high) {
+        (true, true) => Bank0GpioRegisterAccess::write_output_set(1 << pin),
+        (true, false) => Bank0GpioRegisterAccess::write_output_clear(1 << pin),
+        (false, true) => Bank1GpioRegisterAccess::write_output_set(1 << (pin - 32)),
+        (false, false) => Bank1GpioRegisterAccess::write_output_clear(1 << (pin - 32)),
+    }
+}
+
+#[cfg(esp32)]
+fn is_pin_high(pin: u8) -> bool {
+    use crate::gpio::{Bank0GpioRegisterAccess, Bank1GpioRegisterAccess, BankGpioRegisterAccess};
+
+    if pin < 32 {
+        Bank0GpioRegisterAccess::read_input() & (1 << pin) != 0
+    } else {
+        Bank1GpioRegisterAccess::read_input() & (1 << (pin - 32)) != 0
+    }
+}
+
+/// Busy waits for (at least) half an SCL period of a 100 kHz bus, assuming the
+/// highest possible CPU clock
+fn bus_recovery_delay() {
+    #[cfg(xtensa)]
+    xtensa_lx::timer::delay(1200);
+
+    #[cfg(riscv)]
+    unsafe {
+        riscv::asm::delay(800);
+    }
+}
+
+/// Address of an I2C slave device
+///
+/// Used to address a slave in master mode, and as the own address of the
+/// peripheral in slave mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlaveAddress {
+    /// 7-bit address
+    SevenBit(u8),
+    /// 10-bit address
+    TenBit(u16),
+}
+
+impl From<u8> for SlaveAddress {
+    fn from(address: u8) -> Self {
+        SlaveAddress::SevenBit(address)
+    }
+}
+
+impl From<u16> for SlaveAddress {
+    fn from(address: u16) -> Self {
+        SlaveAddress::TenBit(address)
+    }
+}
+
+/// Outcome of a transfer initiated by a master, as reported by
+/// [`I2CSlave::poll`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlaveTransfer {
+    /// Number of bytes written by the master and stored in the receive buffer
+    pub received: usize,
+    /// Number of queued bytes read by the master
+    pub transmitted: usize,
+}
+
+/// I2C peripheral container operating in slave (target) mode
+///
+/// Bytes written by a master are collected from the RX FIFO by
+/// [`poll`](I2CSlave::poll). Bytes a master reads have to be queued in
+/// advance with [`write`](I2CSlave::write).
+pub struct I2CSlave<T> {
+    peripheral: T,
+    rx_count: usize,
+    tx_count: usize,
+}
+
+impl<T> I2CSlave<T>
+where
+    T: Instance,
+{
+    /// Create a new I2C instance in slave mode, listening to `address`
+    /// This will enable the peripheral but the peripheral won't get
+    /// automatically disabled when this gets dropped.
+    pub fn new<SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
+        i2c: T,
+        mut sda: SDA,
+        mut scl: SCL,
+        address: SlaveAddress,
+        peripheral_clock_control: &mut PeripheralClockControl,
+    ) -> Result<Self, SetupError> {
+        enable_peripheral(&i2c, peripheral_clock_control);
+
+        let mut i2c = I2CSlave {
+            peripheral: i2c,
+            rx_count: 0,
+            tx_count: 0,
+        };
+
+        connect_pins(&i2c.peripheral, &mut sda, &mut scl);
+
+        i2c.peripheral.setup_slave(address)?;
 
         Ok(i2c)
     }
 
+    /// Queue bytes to be sent when a master reads from this device
+    ///
+    /// Returns the number of bytes which fit into the TX FIFO. Queued bytes
+    /// which are not read by the master are kept for the next read.
+    pub fn write(&mut self, bytes: &[u8]) -> usize {
+        let register_block = self.peripheral.register_block();
+
+        let queued = register_block.sr.read().txfifo_cnt().bits() as usize;
+        let count = usize::min(I2C_FIFO_SIZE.saturating_sub(queued), bytes.len());
+
+        for byte in &bytes[..count] {
+            write_fifo(register_block, *byte);
+        }
+        self.tx_count += count;
+
+        count
+    }
+
+    /// Check for a finished transfer initiated by a master
+    ///
+    /// Moves received bytes from the RX FIFO into `buffer`. Since a transfer
+    /// can be longer than the FIFO, this has to be called repeatedly with the
+    /// same `buffer` until the master terminates the transfer with a STOP.
+    /// Returns `nb::Error::WouldBlock` until then. Received bytes which don't
+    /// fit into `buffer` are dropped.
+    pub fn poll(&mut self, buffer: &mut [u8]) -> nb::Result<SlaveTransfer, Error> {
+        let register_block = self.peripheral.register_block();
+        let interrupts = register_block.int_raw.read();
+
+        if interrupts.rxfifo_ovf_int_raw().bit_is_set() {
+            register_block
+                .int_clr
+                .write(|w| w.rxfifo_ovf_int_clr().set_bit());
+            self.rx_count = 0;
+            self.peripheral.reset_fifo();
+            self.tx_count = 0;
+
+            return Err(nb::Error::Other(Error::ExceedingFifo));
+        }
+
+        drain_rx_fifo(register_block, buffer, &mut self.rx_count);
+
+        if interrupts.trans_complete_int_raw().bit_is_clear() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        register_block
+            .int_clr
+            .write(|w| w.trans_complete_int_clr().set_bit());
+
+        // Bytes might have been received between draining the FIFO and the STOP
+        drain_rx_fifo(register_block, buffer, &mut self.rx_count);
+
+        let transmitted = if register_block.sr.read().slave_rw().bit_is_set() {
+            // The master read from us, drop what it didn't read so the next read
+            // starts with freshly queued data
+            let remaining = register_block.sr.read().txfifo_cnt().bits() as usize;
+            register_block
+                .fifo_conf
+                .modify(|_, w| w.tx_fifo_rst().set_bit());
+            register_block
+                .fifo_conf
+                .modify(|_, w| w.tx_fifo_rst().clear_bit());
+
+            let transmitted = self.tx_count.saturating_sub(remaining);
+            self.tx_count = 0;
+            transmitted
+        } else {
+            0
+        };
+
+        let received = usize::min(self.rx_count, buffer.len());
+        self.rx_count = 0;
+
+        Ok(SlaveTransfer {
+            received,
+            transmitted,
+        })
+    }
+
     /// Return the raw interface to the underlying peripheral
     pub fn free(self) -> T {
         self.peripheral
     }
 }
 
+/// Moves all bytes from the RX FIFO into `buffer`, starting at `count`
+fn drain_rx_fifo(register_block: &RegisterBlock, buffer: &mut [u8], count: &mut usize) {
+    let available = register_block.sr.read().rxfifo_cnt().bits();
+
+    for _ in 0..available {
+        let byte = read_fifo(register_block);
+        if let Some(slot) = buffer.get_mut(*count) {
+            *slot = byte;
+        }
+        *count += 1;
+    }
+}
+
+/// Configures SDA and SCL as open drain pins and routes them to the
+/// peripheral
+fn connect_pins<T: Instance, SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
+    i2c: &T,
+    sda: &mut SDA,
+    scl: &mut SCL,
+) {
+    sda.set_to_open_drain_output()
+        .enable_input(true)
+        .internal_pull_up(true)
+        .connect_peripheral_to_output(i2c.sda_output_signal())
+        .connect_input_to_peripheral(i2c.sda_input_signal());
+
+    scl.set_to_open_drain_output()
+        .enable_input(true)
+        .internal_pull_up(true)
+        .connect_peripheral_to_output(i2c.scl_output_signal())
+        .connect_input_to_peripheral(i2c.scl_input_signal());
+}
+
 fn enable_peripheral<T: Instance>(i2c: &T, peripheral_clock_control: &mut PeripheralClockControl) {
     // enable peripheral
     match i2c.i2c_number() {
@@ -317,22 +1129,82 @@ fn enable_peripheral<T: Instance>(i2c: &T, peripheral_clock_control: &mut Periph
     }
 }
 
-/// I2C Peripheral Instance
-pub trait Instance {
-    fn register_block(&self) -> &RegisterBlock;
+/// I2C Peripheral Instance
+pub trait Instance {
+    fn register_block(&self) -> &RegisterBlock;
+
+    fn i2c_number(&self) -> usize;
+
+    fn scl_output_signal(&self) -> OutputSignal;
+
+    fn scl_input_signal(&self) -> InputSignal;
+
+    fn sda_output_signal(&self) -> OutputSignal;
+
+    fn sda_input_signal(&self) -> InputSignal;
+
+    fn setup(&mut self, config: &I2cConfig, clocks: &Clocks) -> Result<(), SetupError> {
+        // Reset entire peripheral (also resets fifo)
+        self.reset();
+
+        self.register_block().ctr.modify(|_, w| unsafe {
+            // Clear register
+            w.bits(0)
+                // Set I2C controller to master mode
+                .ms_mode()
+                .set_bit()
+                // Use open drain output for SDA and SCL
+                .sda_force_out()
+                .set_bit()
+                .scl_force_out()
+                .set_bit()
+                // Use Most Significant Bit first for sending and receiving data
+                .tx_lsb_first()
+                .clear_bit()
+                .rx_lsb_first()
+                .clear_bit()
+                // Ensure that clock is enabled
+                .clk_en()
+                .set_bit()
+        });
+
+        #[cfg(esp32s2)]
+        self.register_block()
+            .ctr
+            .modify(|_, w| w.ref_always_on().set_bit());
+
+        // Configure filter
+        self.set_filter(Some(7), Some(7));
+
+        // Configure frequency
+        self.set_frequency(clocks.i2c_clock.convert(), config.frequency)?;
+
+        // Configure timeouts
+        self.set_timeout(clocks.i2c_clock.convert(), config.frequency, config.timeout)?;
+        #[cfg(any(esp32c3, esp32s3))]
+        self.set_stretch_limit(
+            clocks.i2c_clock.convert(),
+            config.frequency,
+            config.stretch_limit,
+        )?;
 
-    fn i2c_number(&self) -> usize;
+        // Propagate configuration changes (only necessary with C3 and S3)
+        #[cfg(any(esp32c3, esp32s3))]
+        self.register_block()
+            .ctr
+            .modify(|_, w| w.conf_upgate().set_bit());
+
+        Ok(())
+    }
 
-    fn setup(&mut self, frequency: HertzU32, clocks: &Clocks) -> Result<(), SetupError> {
+    /// Configures the peripheral as an I2C slave listening to `address`
+    fn setup_slave(&mut self, address: SlaveAddress) -> Result<(), SetupError> {
         // Reset entire peripheral (also resets fifo)
         self.reset();
 
         self.register_block().ctr.modify(|_, w| unsafe {
-            // Clear register
+            // Clear register, this also sets the I2C controller to slave mode
             w.bits(0)
-                // Set I2C controller to master mode
-                .ms_mode()
-                .set_bit()
                 // Use open drain output for SDA and SCL
                 .sda_force_out()
                 .set_bit()
@@ -348,16 +1220,74 @@ pub trait Instance {
                 .set_bit()
         });
 
+        // Start sending the TX FIFO contents as soon as a master addresses us for
+        // reading (only necessary with C3 and S3)
+        #[cfg(any(esp32c3, esp32s3))]
+        self.register_block()
+            .ctr
+            .modify(|_, w| w.slv_tx_auto_start_en().set_bit());
+
         #[cfg(esp32s2)]
         self.register_block()
             .ctr
             .modify(|_, w| w.ref_always_on().set_bit());
 
+        // Received bytes are always stored in the FIFO, never interpreted as
+        // an address
+        self.register_block()
+            .fifo_conf
+            .modify(|_, w| w.nonfifo_en().clear_bit().fifo_addr_cfg_en().clear_bit());
+
+        match address {
+            SlaveAddress::SevenBit(address) => {
+                if address > 0x7f {
+                    return Err(SetupError::InvalidAddress);
+                }
+
+                self.register_block().slave_addr.write(|w| unsafe {
+                    w.slave_addr()
+                        .bits(address as u16)
+                        .addr_10bit_en()
+                        .clear_bit()
+                });
+            }
+            SlaveAddress::TenBit(address) => {
+                if address > 0x3ff {
+                    return Err(SetupError::InvalidAddress);
+                }
+
+                // C3 and S3 expect the address in the order it is sent on the
+                // wire: the lower 8 bits followed by the `11110` prefix and the
+                // upper 2 bits
+                #[cfg(any(esp32c3, esp32s3))]
+                let address = (address & 0xff) << 7 | 0x78 | (address >> 8);
+
+                self.register_block()
+                    .slave_addr
+                    .write(|w| unsafe { w.slave_addr().bits(address).addr_10bit_en().set_bit() });
+
+                #[cfg(any(esp32c3, esp32s3))]
+                self.register_block()
+                    .ctr
+                    .modify(|_, w| w.addr_10bit_rw_check_en().set_bit());
+            }
+        }
+
         // Configure filter
         self.set_filter(Some(7), Some(7));
 
-        // Configure frequency
-        self.set_frequency(clocks.i2c_clock.convert(), frequency)?;
+        // The SDA timings are given in cycles of the (undivided) source clock
+        #[cfg(any(esp32s3, esp32c3))]
+        self.register_block()
+            .clk_conf
+            .modify(|_, w| unsafe { w.sclk_sel().clear_bit().sclk_div_num().bits(0) });
+
+        self.register_block()
+            .sda_hold
+            .write(|w| unsafe { w.time().bits(10) });
+        self.register_block()
+            .sda_sample
+            .write(|w| unsafe { w.time().bits(10) });
 
         // Propagate configuration changes (only necessary with C3 and S3)
         #[cfg(any(esp32c3, esp32s3))]
@@ -395,6 +1325,73 @@ pub trait Instance {
             .modify(|_, w| w.fsm_rst().set_bit());
     }
 
+    /// Generates up to 9 SCL pulses followed by a STOP condition to release a
+    /// slave holding SDA low
+    #[cfg(not(esp32))]
+    fn clear_bus(&mut self) {
+        // The hardware clears the enable bit after sending the pulses
+        self.register_block()
+            .scl_sp_conf
+            .modify(|_, w| unsafe { w.scl_rst_slv_num().bits(9).scl_rst_slv_en().set_bit() });
+        #[cfg(any(esp32c3, esp32s3))]
+        self.register_block()
+            .ctr
+            .modify(|_, w| w.conf_upgate().set_bit());
+
+        while self
+            .register_block()
+            .scl_sp_conf
+            .read()
+            .scl_rst_slv_en()
+            .bit_is_set()
+        {
+            // wait
+        }
+
+        // The pulses are not followed by a STOP, so generate one by forcing the
+        // lines low: SCL low, SDA low, SCL released, SDA released
+        let steps = [(true, false), (true, true), (false, true), (false, false)];
+        for (scl_low, sda_low) in steps {
+            self.register_block()
+                .scl_sp_conf
+                .modify(|_, w| w.scl_pd_en().bit(scl_low).sda_pd_en().bit(sda_low));
+            #[cfg(any(esp32c3, esp32s3))]
+            self.register_block()
+                .ctr
+                .modify(|_, w| w.conf_upgate().set_bit());
+
+            bus_recovery_delay();
+        }
+    }
+
+    /// Determines which part of a transmission was not acknowledged, based on
+    /// the first WRITE command which was not completed
+    fn ack_failure(&self) -> AckFailure {
+        let mut previous_was_start = false;
+
+        for cmd in self.register_block().comd.iter() {
+            let cmd = cmd.read();
+            let opcode = cmd.command().bits() >> 11;
+
+            if cmd.command_done().bit_is_clear() {
+                // The address is always written right after a (repeated) START
+                if opcode == Opcode::Write as u16 {
+                    return if previous_was_start {
+                        AckFailure::Address
+                    } else {
+                        AckFailure::Data
+                    };
+                }
+
+                break;
+            }
+
+            previous_was_start = opcode == Opcode::RStart as u16;
+        }
+
+        AckFailure::Unknown
+    }
+
     /// Resets the I2C peripheral's command registers
     fn reset_command_list(&mut self) {
         // Confirm that all commands that were configured were actually executed
@@ -440,17 +1437,8 @@ pub trait Instance {
         source_clk: HertzU32,
         bus_freq: HertzU32,
     ) -> Result<(), SetupError> {
-        cfg_if::cfg_if! {
-            if #[cfg(any(esp32s3, esp32c3))] {
-                // C3 and S3 have a clock divider mechanism, which we want to configure
-                // as high as possible.
-                let sclk_div = source_clk.raw() / (bus_freq.raw() * 1024) + 1;
-                let half_cycle = source_clk.raw() / sclk_div as u32 / bus_freq.raw() / 2;
-            } else {
-                // For EPS32 and the S2 variant no clock divider mechanism exists.
-                let half_cycle = source_clk.raw() / bus_freq.raw() / 2;
-            }
-        }
+        #[cfg_attr(any(esp32, esp32s2), allow(unused_variables))]
+        let (sclk_div, half_cycle) = clock_timing(source_clk, bus_freq);
 
         // The different chips have highly very different timing configurations, so
         // we're setting these up separately (this might introduce some overhead,
@@ -477,7 +1465,6 @@ pub trait Instance {
                 let scl_high = half_cycle;
                 let sda_hold = half_cycle / 2;
                 let sda_sample = scl_high / 2;
-                let tout = half_cycle * 20;
             }
         }
 
@@ -577,22 +1564,83 @@ pub trait Instance {
             self.register_block()
                 .scl_stop_hold
                 .write(|w| w.time().bits(hold));
+        }
 
-            // The ESP32 variant does not have an enable flag for the
-            // timeout mechanism
-            cfg_if::cfg_if! {
-                if #[cfg(esp32)] {
-                    // timeout
-                    self.register_block()
+        Ok(())
+    }
+
+    /// Sets the number of SCL periods the bus may remain unchanged during a
+    /// transmission before it is aborted
+    ///
+    /// The hardware counts in (divided) source clock cycles, so `timeout` is
+    /// converted using the SCL half period of `bus_freq`. The C3 and S3 only
+    /// support powers of two, there the value is rounded up.
+    fn set_timeout(
+        &mut self,
+        source_clk: HertzU32,
+        bus_freq: HertzU32,
+        timeout: Option<u32>,
+    ) -> Result<(), SetupError> {
+        let (_, half_cycle) = clock_timing(source_clk, bus_freq);
+
+        // Timeout in (divided) source clock cycles
+        let cycles = match timeout {
+            Some(periods) => Some(
+                periods
+                    .checked_mul(2 * half_cycle)
+                    .ok_or(SetupError::InvalidTimeoutConfig)?,
+            ),
+            None => None,
+        };
+
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                // The ESP32 variant does not have an enable flag for the
+                // timeout mechanism
+                let cycles = cycles.unwrap_or(0xf_ffff);
+                if cycles > 0xf_ffff {
+                    return Err(SetupError::InvalidTimeoutConfig);
+                }
+
+                self.register_block()
+                    .to
+                    .write(|w| unsafe { w.time_out().bits(cycles) });
+            } else if #[cfg(esp32s2)] {
+                match cycles {
+                    Some(cycles) => {
+                        if cycles > 0xff_ffff {
+                            return Err(SetupError::InvalidTimeoutConfig);
+                        }
+
+                        self.register_block().to.write(|w| unsafe {
+                            w.time_out_value().bits(cycles).time_out_en().set_bit()
+                        });
+                    }
+                    None => self
+                        .register_block()
                         .to
-                        .write(|w| w.time_out().bits(tout.into()));
+                        .write(|w| w.time_out_en().clear_bit()),
                 }
-                else {
-                    // timeout
-                    // FIXME: Enable timout for other chips!
-                    self.register_block()
+            } else {
+                // C3 and S3 configure the timeout as a power of two
+                match cycles {
+                    Some(cycles) => {
+                        let exponent = timeout_exponent(cycles);
+                        if exponent > 0x1f {
+                            return Err(SetupError::InvalidTimeoutConfig);
+                        }
+
+                        self.register_block().to.write(|w| unsafe {
+                            w.time_out_value()
+                                .bits(exponent as u8)
+                                .time_out_en()
+                                .set_bit()
+                        });
+                    }
+                    None => self
+                        .register_block()
                         .to
-                        .write(|w| w.time_out_en().clear_bit());
+                        .write(|w| w.time_out_en().clear_bit()),
                 }
             }
         }
@@ -600,11 +1648,49 @@ pub trait Instance {
         Ok(())
     }
 
+    /// Sets the number of SCL periods a slave may stretch the clock before the
+    /// transmission is aborted
+    ///
+    /// Like the timeout this is converted to a power of two of (divided)
+    /// source clock cycles, rounded up.
+    #[cfg(any(esp32c3, esp32s3))]
+    fn set_stretch_limit(
+        &mut self,
+        source_clk: HertzU32,
+        bus_freq: HertzU32,
+        stretch_limit: Option<u32>,
+    ) -> Result<(), SetupError> {
+        let (_, half_cycle) = clock_timing(source_clk, bus_freq);
+
+        // The limit is configured as a power of two of (divided) source clock
+        // cycles, the hardware supports at most 2^23 cycles
+        let exponent = match stretch_limit {
+            Some(periods) => periods
+                .checked_mul(2 * half_cycle)
+                .map(timeout_exponent)
+                .filter(|exponent| *exponent <= 23)
+                .ok_or(SetupError::InvalidTimeoutConfig)?,
+            None => 23,
+        };
+
+        self.register_block()
+            .scl_st_time_out
+            .write(|w| unsafe { w.scl_st_to_i2c().bits(exponent as u8) });
+
+        Ok(())
+    }
+
     /// Start the actual transmission on a previously configured command set
     ///
     /// This includes the monitoring of the execution in the peripheral and the
     /// return of the operation outcome, including error states
     fn execute_transmission(&mut self) -> Result<(), Error> {
+        self.start_transmission();
+        nb::block!(self.check_transmission())
+    }
+
+    /// Starts executing the command list without waiting for it to finish
+    fn start_transmission(&mut self) {
         // Clear all I2C interrupts
         self.register_block()
             .int_clr
@@ -621,179 +1707,216 @@ pub trait Instance {
         self.register_block()
             .ctr
             .modify(|_, w| w.trans_start().set_bit());
+    }
 
-        loop {
-            let interrupts = self.register_block().int_raw.read();
+    /// Checks whether the command list started by `start_transmission` has
+    /// been executed
+    ///
+    /// Returns `nb::Error::WouldBlock` as long as the transmission is in
+    /// progress.
+    fn check_transmission(&self) -> nb::Result<(), Error> {
+        let interrupts = self.register_block().int_raw.read();
 
-            // The ESP32 variant has a slightly different interrupt naming
-            // scheme!
-            cfg_if::cfg_if! {
-                if #[cfg(esp32)] {
-                    // Handle error cases
-                    if interrupts.time_out_int_raw().bit_is_set() {
-                        return Err(Error::TimeOut);
-                    } else if interrupts.ack_err_int_raw().bit_is_set() {
-                        return Err(Error::AckCheckFailed);
-                    } else if interrupts.arbitration_lost_int_raw().bit_is_set() {
-                        return Err(Error::ArbitrationLost);
-                    }
+        // The ESP32 variant has a slightly different interrupt naming
+        // scheme!
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                // Handle error cases
+                if interrupts.time_out_int_raw().bit_is_set() {
+                    return Err(nb::Error::Other(Error::TimeOut));
+                } else if interrupts.ack_err_int_raw().bit_is_set() {
+                    return Err(nb::Error::Other(Error::AckCheckFailed(self.ack_failure())));
+                } else if interrupts.arbitration_lost_int_raw().bit_is_set() {
+                    return Err(nb::Error::Other(Error::ArbitrationLost));
                 }
-                else {
-                    // Handle error cases
-                    if interrupts.time_out_int_raw().bit_is_set() {
-                        return Err(Error::TimeOut);
-                    } else if interrupts.nack_int_raw().bit_is_set() {
-                        return Err(Error::AckCheckFailed);
-                    } else if interrupts.arbitration_lost_int_raw().bit_is_set() {
-                        return Err(Error::ArbitrationLost);
-                    }
+            }
+            else {
+                // Handle error cases
+                if interrupts.time_out_int_raw().bit_is_set() {
+                    return Err(nb::Error::Other(Error::TimeOut));
+                } else if interrupts.nack_int_raw().bit_is_set() {
+                    return Err(nb::Error::Other(Error::AckCheckFailed(self.ack_failure())));
+                } else if interrupts.arbitration_lost_int_raw().bit_is_set() {
+                    return Err(nb::Error::Other(Error::ArbitrationLost));
                 }
             }
+        }
 
-            // Handle completion cases
-            // A full transmission was completed
-            if interrupts.trans_complete_int_raw().bit_is_set()
-                || interrupts.end_detect_int_raw().bit_is_set()
-            {
-                break;
-            }
+        // Only the C3 and S3 variants limit clock stretching separately
+        #[cfg(any(esp32c3, esp32s3))]
+        if interrupts.scl_st_to_int_raw().bit_is_set() {
+            return Err(nb::Error::Other(Error::ClockStretchTimeout));
+        }
+
+        // Handle completion cases
+        // A full transmission was completed
+        if interrupts.trans_complete_int_raw().bit_is_clear()
+            && interrupts.end_detect_int_raw().bit_is_clear()
+        {
+            return Err(nb::Error::WouldBlock);
         }
 
         // Confirm that all commands that were configured were actually executed
         for cmd in self.register_block().comd.iter() {
             if cmd.read().command().bits() != 0x0 && cmd.read().command_done().bit_is_clear() {
-                return Err(Error::ExecIncomplete);
+                return Err(nb::Error::Other(Error::ExecIncomplete));
             }
         }
 
         Ok(())
     }
 
-    fn add_write_operation<'a, I>(
+    /// Fills the command list and the TX FIFO with (at most) one FIFO worth of
+    /// a write operation
+    ///
+    /// If `start` is set, the operation begins with a (repeated) START
+    /// condition followed by the address. The command list is not terminated,
+    /// this is up to the caller. Returns `true` if all bytes have been loaded.
+    fn add_write_operation<'a, I, B>(
         &self,
-        addr: u8,
-        bytes: &[u8],
+        addr: SlaveAddress,
+        bytes: &mut Peekable<B>,
         cmd_iterator: &mut I,
-        include_stop: bool,
-    ) -> Result<(), Error>
+        start: bool,
+    ) -> Result<bool, Error>
     where
         I: Iterator<Item = &'a COMD>,
+        B: Iterator<Item = u8>,
     {
-        // Check if we have another cmd register ready, otherwise return appropriate
-        // error
-        let cmd_start = cmd_iterator.next().ok_or(Error::CommandNrExceeded)?;
-        // RSTART command
-        cmd_start.write(|w| unsafe { w.command().bits(Command::Start.into()) });
-
-        // Load address and R/W bit into FIFO
-        write_fifo(
-            self.register_block(),
-            addr << 1 | OperationType::Write as u8,
-        );
-        // Load actual data bytes
-        for byte in bytes {
-            write_fifo(self.register_block(), *byte);
-        }
-
-        // Check if we have another cmd register ready, otherwise return appropriate
-        // error
-        let cmd_write = cmd_iterator.next().ok_or(Error::CommandNrExceeded)?;
-        // WRITE command
-        cmd_write.write(|w| unsafe {
-            w.command().bits(
+        let mut capacity = I2C_FIFO_SIZE;
+
+        if start {
+            // RSTART command
+            add_cmd(cmd_iterator, Command::Start)?;
+
+            // Load address and R/W bit into FIFO
+            let length = write_address(self.register_block(), addr, OperationType::Write)?;
+            capacity -= length as usize;
+
+            // WRITE command for the address
+            add_cmd(
+                cmd_iterator,
                 Command::Write {
                     ack_exp: Ack::Ack,
                     ack_check_en: true,
-                    length: 1 + bytes.len() as u8,
-                }
-                .into(),
-            )
-        });
+                    length,
+                },
+            )?;
+        }
 
-        if include_stop {
-            // Check if we have another cmd register ready, otherwise return appropriate
-            // error
-            let cmd_stop = cmd_iterator.next().ok_or(Error::CommandNrExceeded)?;
-            // STOP command
-            cmd_stop.write(|w| unsafe { w.command().bits(Command::Stop.into()) });
+        // Load as many data bytes as the FIFO can take
+        let mut length = 0;
+        while length < capacity {
+            match bytes.next() {
+                Some(byte) => write_fifo(self.register_block(), byte),
+                None => break,
+            }
+            length += 1;
         }
 
-        Ok(())
+        if length > 0 {
+            // WRITE command for the data bytes
+            add_cmd(
+                cmd_iterator,
+                Command::Write {
+                    ack_exp: Ack::Ack,
+                    ack_check_en: true,
+                    length: length as u8,
+                },
+            )?;
+        }
+
+        Ok(bytes.peek().is_none())
     }
 
+    /// Fills the command list for a read operation of `length` bytes
+    ///
+    /// `length` must not exceed the size of the RX FIFO. If `start` is set, the
+    /// operation begins with a (repeated) START condition followed by the
+    /// address. `addressed` tells if the slave was already addressed by a
+    /// write in the same transfer, which saves resending a full 10-bit address.
+    /// The last byte is NACKed unless `ack_last` is set, which is
+    /// needed if the read continues afterwards. The command list is not
+    /// terminated, this is up to the caller.
     fn add_read_operation<'a, I>(
         &self,
-        addr: u8,
-        buffer: &[u8],
+        addr: SlaveAddress,
+        length: usize,
         cmd_iterator: &mut I,
+        start: bool,
+        addressed: bool,
+        ack_last: bool,
     ) -> Result<(), Error>
     where
         I: Iterator<Item = &'a COMD>,
     {
-        // Check if we have another cmd register ready, otherwise return appropriate
-        // error
-        cmd_iterator
-            .next()
-            .ok_or(Error::CommandNrExceeded)?
-            .write(|w| unsafe { w.command().bits(Command::Start.into()) });
-
-        // Load address and R/W bit into FIFO
-        write_fifo(self.register_block(), addr << 1 | OperationType::Read as u8);
-
-        // Check if we have another cmd register ready, otherwise return appropriate
-        // error
-        cmd_iterator
-            .next()
-            .ok_or(Error::CommandNrExceeded)?
-            .write(|w| unsafe {
-                w.command().bits(
+        if start {
+            // Reading from a 10-bit address requires sending the full address
+            // with the write bit first, followed by a repeated START with just
+            // the first address byte and the read bit
+            if matches!(addr, SlaveAddress::TenBit(_)) && !addressed {
+                add_cmd(cmd_iterator, Command::Start)?;
+
+                let length = write_address(self.register_block(), addr, OperationType::Write)?;
+                add_cmd(
+                    cmd_iterator,
                     Command::Write {
                         ack_exp: Ack::Ack,
                         ack_check_en: true,
-                        length: 1,
-                    }
-                    .into(),
-                )
-            });
+                        length,
+                    },
+                )?;
+            }
 
-        // For reading bytes prior to the last read byte we need to
-        // configure the ack
-        if buffer.len() > 1 {
-            // READ command for first n - 1 bytes
-            cmd_iterator
-                .next()
-                .ok_or(Error::CommandNrExceeded)?
-                .write(|w| unsafe {
-                    w.command().bits(
-                        Command::Read {
-                            ack_value: Ack::Ack,
-                            length: buffer.len() as u8 - 1,
-                        }
-                        .into(),
-                    )
-                });
+            // RSTART command
+            add_cmd(cmd_iterator, Command::Start)?;
+
+            // Load address and R/W bit into FIFO
+            let length = write_address(self.register_block(), addr, OperationType::Read)?;
+
+            // WRITE command for the address
+            add_cmd(
+                cmd_iterator,
+                Command::Write {
+                    ack_exp: Ack::Ack,
+                    ack_check_en: true,
+                    length,
+                },
+            )?;
         }
 
-        // READ command for (last or only) byte
-        cmd_iterator
-            .next()
-            .ok_or(Error::CommandNrExceeded)?
-            .write(|w| unsafe {
-                w.command().bits(
+        if ack_last {
+            // READ command for all bytes
+            add_cmd(
+                cmd_iterator,
+                Command::Read {
+                    ack_value: Ack::Ack,
+                    length: length as u8,
+                },
+            )?;
+        } else {
+            // For reading bytes prior to the last read byte we need to
+            // configure the ack
+            if length > 1 {
+                // READ command for first n - 1 bytes
+                add_cmd(
+                    cmd_iterator,
                     Command::Read {
-                        ack_value: Ack::Nack,
-                        length: 1,
-                    }
-                    .into(),
-                )
-            });
+                        ack_value: Ack::Ack,
+                        length: length as u8 - 1,
+                    },
+                )?;
+            }
 
-        // Check if we have another cmd register ready, otherwise return appropriate
-        // error
-        cmd_iterator
-            .next()
-            .ok_or(Error::CommandNrExceeded)?
-            .write(|w| unsafe { w.command().bits(Command::Stop.into()) });
+            // READ command for (last or only) byte
+            add_cmd(
+                cmd_iterator,
+                Command::Read {
+                    ack_value: Ack::Nack,
+                    length: 1,
+                },
+            )?;
+        }
 
         Ok(())
     }
@@ -809,100 +1932,233 @@ pub trait Instance {
             .modify(|_, w| w.tx_fifo_rst().clear_bit().rx_fifo_rst().clear_bit());
     }
 
-    /// Send data bytes from the `bytes` array to a target slave with the
-    /// address `addr`
-    fn master_write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
-        // Reset FIFO and command list
-        self.reset_fifo();
-        self.reset_command_list();
+    /// Executes a write operation, which is split into several command list
+    /// refills if the data does not fit into the FIFO
+    ///
+    /// If `start` is set, a (repeated) START and the address are sent first. If
+    /// `stop` is set, a STOP is sent after the last byte, otherwise the
+    /// transmission is paused so that another operation can continue it.
+    fn write_operation<B>(
+        &mut self,
+        addr: SlaveAddress,
+        bytes: &mut Peekable<B>,
+        start: bool,
+        stop: bool,
+    ) -> Result<(), Error>
+    where
+        B: Iterator<Item = u8>,
+    {
+        // Nothing to do for an empty write in the middle of a transaction
+        if bytes.peek().is_none() && !start && !stop {
+            return Ok(());
+        }
+
+        let mut start = start;
+        loop {
+            // Reset FIFO and command list
+            self.reset_fifo();
+            self.reset_command_list();
 
-        // Split the potentially larger `bytes` array into chunks of (at most) 31
-        // entries Together with the addr/access byte at the beginning of every
-        // transmission, this is the maximum size that we can store in the
-        // (default config) TX FIFO
-        for chunk in bytes.chunks(31) {
-            // Add write operation
-            self.add_write_operation(addr, chunk, &mut self.register_block().comd.iter(), true)?;
+            let mut cmd_iterator = self.register_block().comd.iter();
+            let done = self.add_write_operation(addr, bytes, &mut cmd_iterator, start)?;
+
+            // Either finish the transaction or pause it so it can be continued
+            // after refilling the FIFO
+            if done && stop {
+                add_cmd(&mut cmd_iterator, Command::Stop)?;
+            } else {
+                add_cmd(&mut cmd_iterator, Command::End)?;
+            }
 
             // Start transmission
-            self.execute_transmission()?
-        }
+            self.execute_transmission()?;
 
-        Ok(())
+            if done {
+                return Ok(());
+            }
+
+            start = false;
+        }
     }
 
-    /// Read bytes from a target slave with the address `addr`
-    /// The number of read bytes is deterimed by the size of the `buffer`
-    /// argument
-    fn master_read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
-        // If the buffer size is > 32 bytes, this signals the
-        // intent to read more than that number of bytes, which we
-        // cannot achieve at this point in time
-        // TODO: Handle the case where we transfer an amount of data that is exceeding
-        // the FIFO size (i.e. > 32 bytes?)
-        if buffer.len() > 31 {
-            return Err(Error::ExceedingFifo);
+    /// Executes a read operation, which is split into several command list
+    /// refills if `buffer` does not fit into the FIFO
+    ///
+    /// If `start` is set, a (repeated) START and the address are sent first.
+    /// `addressed` tells if a preceding write of the same transfer already
+    /// addressed the slave, see `add_read_operation`. If `stop` is set, a STOP
+    /// is sent after the last byte. If `will_continue` is set, the last byte
+    /// is ACKed as the following operation continues reading.
+    fn read_operation(
+        &mut self,
+        addr: SlaveAddress,
+        buffer: &mut [u8],
+        start: bool,
+        addressed: bool,
+        stop: bool,
+        will_continue: bool,
+    ) -> Result<(), Error> {
+        // A read needs at least one byte, only terminate the transaction if
+        // requested
+        if buffer.is_empty() {
+            if stop {
+                self.reset_fifo();
+                self.reset_command_list();
+                add_cmd(&mut self.register_block().comd.iter(), Command::Stop)?;
+                self.execute_transmission()?;
+            }
+
+            return Ok(());
         }
 
-        // Reset FIFO and command list
-        self.reset_fifo();
-        self.reset_command_list();
+        let mut start = start;
+        let mut chunks = buffer.chunks_mut(I2C_FIFO_SIZE).peekable();
+        while let Some(chunk) = chunks.next() {
+            let last_chunk = chunks.peek().is_none();
+
+            // Reset FIFO and command list
+            self.reset_fifo();
+            self.reset_command_list();
+
+            let mut cmd_iterator = self.register_block().comd.iter();
+            self.add_read_operation(
+                addr,
+                chunk.len(),
+                &mut cmd_iterator,
+                start,
+                addressed,
+                !last_chunk || will_continue,
+            )?;
+
+            if last_chunk && stop {
+                add_cmd(&mut cmd_iterator, Command::Stop)?;
+            } else {
+                add_cmd(&mut cmd_iterator, Command::End)?;
+            }
 
-        // Add write operation
-        self.add_read_operation(addr, buffer, &mut self.register_block().comd.iter())?;
+            // Start transmission
+            self.execute_transmission()?;
 
-        // Start transmission
-        self.execute_transmission()?;
+            // Read bytes from FIFO
+            // FIXME: Handle case where less data has been provided by the slave than
+            // requested? Or is this prevented from a protocol perspective?
+            for byte in chunk.iter_mut() {
+                *byte = read_fifo(self.register_block());
+            }
 
-        // Read bytes from FIFO
-        // FIXME: Handle case where less data has been provided by the slave than
-        // requested? Or is this prevented from a protocol perspective?
-        for byte in buffer.iter_mut() {
-            *byte = read_fifo(self.register_block());
+            start = false;
         }
 
         Ok(())
     }
 
+    /// Send data bytes from the `bytes` array to a target slave with the
+    /// address `addr`
+    fn master_write(&mut self, addr: SlaveAddress, bytes: &[u8]) -> Result<(), Error> {
+        self.write_operation(addr, &mut bytes.iter().copied().peekable(), true, true)
+    }
+
+    /// Read bytes from a target slave with the address `addr`
+    /// The number of read bytes is deterimed by the size of the `buffer`
+    /// argument
+    fn master_read(&mut self, addr: SlaveAddress, buffer: &mut [u8]) -> Result<(), Error> {
+        self.read_operation(addr, buffer, true, false, true, false)
+    }
+
     /// Write bytes from the `bytes` array first and then read n bytes into
     /// the `buffer` array with n being the size of the array.
     fn master_write_read(
         &mut self,
-        addr: u8,
+        addr: SlaveAddress,
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Error> {
-        // If the buffer or bytes size is > 32 bytes, this signals the
-        // intent to read/write more than that number of bytes, which we
-        // cannot achieve at this point in time
-        // TODO: Handle the case where we transfer an amount of data that is exceeding
-        // the FIFO size (i.e. > 32 bytes?)
-        if buffer.len() > 31 || bytes.len() > 31 {
-            return Err(Error::ExceedingFifo);
-        }
-
-        // Reset FIFO and command list
-        self.reset_fifo();
-        self.reset_command_list();
+        // The write is paused (END) and then continued with a repeated START for
+        // the read
+        self.write_operation(addr, &mut bytes.iter().copied().peekable(), true, false)?;
+        self.read_operation(addr, buffer, true, true, true, false)
+    }
+}
 
-        let mut cmd_iterator = self.register_block().comd.iter();
+/// Loads the address and R/W bit into the TX FIFO
+///
+/// Returns the number of bytes loaded. A 10-bit address takes two bytes when
+/// writing, but only the first byte is resent for a read after a repeated
+/// START.
+fn write_address(
+    register_block: &RegisterBlock,
+    addr: SlaveAddress,
+    operation: OperationType,
+) -> Result<u8, Error> {
+    match addr {
+        SlaveAddress::SevenBit(addr) => {
+            if addr > 0x7f {
+                return Err(Error::InvalidAddress);
+            }
 
-        // Add write operation
-        self.add_write_operation(addr, bytes, &mut cmd_iterator, false)?;
+            write_fifo(register_block, addr << 1 | operation as u8);
 
-        // Add read operation (which appends commands to the existing command list)
-        self.add_read_operation(addr, buffer, &mut cmd_iterator)?;
+            Ok(1)
+        }
+        SlaveAddress::TenBit(addr) => {
+            if addr > 0x3ff {
+                return Err(Error::InvalidAddress);
+            }
 
-        // Start transmission
-        self.execute_transmission()?;
+            // `11110` prefix followed by the upper two address bits
+            write_fifo(
+                register_block,
+                0xf0 | ((addr >> 7) as u8 & 0x06) | operation as u8,
+            );
 
-        // Read bytes from FIFO
-        for byte in buffer.iter_mut() {
-            *byte = read_fifo(self.register_block());
+            match operation {
+                OperationType::Write => {
+                    write_fifo(register_block, addr as u8);
+                    Ok(2)
+                }
+                OperationType::Read => Ok(1),
+            }
         }
+    }
+}
 
-        Ok(())
+/// Returns the divider applied to the source clock (only C3 and S3 have one)
+/// and the length of half an SCL period in (divided) source clock cycles
+fn clock_timing(source_clk: HertzU32, bus_freq: HertzU32) -> (u32, u32) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(esp32s3, esp32c3))] {
+            // C3 and S3 have a clock divider mechanism, which we want to configure
+            // as high as possible.
+            let sclk_div = source_clk.raw() / (bus_freq.raw() * 1024) + 1;
+        } else {
+            // For EPS32 and the S2 variant no clock divider mechanism exists.
+            let sclk_div = 1;
+        }
     }
+
+    let half_cycle = source_clk.raw() / sclk_div / bus_freq.raw() / 2;
+
+    (sclk_div, half_cycle)
+}
+
+/// Returns the smallest exponent for which `2^exponent >= cycles`
+#[cfg(any(esp32c3, esp32s3))]
+fn timeout_exponent(cycles: u32) -> u32 {
+    u32::BITS - cycles.saturating_sub(1).leading_zeros()
+}
+
+/// Adds a command to the next free command register, or returns an error if
+/// the command list is exhausted
+fn add_cmd<'a, I>(cmd_iterator: &mut I, command: Command) -> Result<(), Error>
+where
+    I: Iterator<Item = &'a COMD>,
+{
+    cmd_iterator
+        .next()
+        .ok_or(Error::CommandNrExceeded)?
+        .write(|w| unsafe { w.command().bits(command.into()) });
+
+    Ok(())
 }
 
 #[cfg(not(any(esp32, esp32s2)))]
@@ -956,6 +2212,26 @@ impl Instance for crate::pac::I2C0 {
     fn i2c_number(&self) -> usize {
         0
     }
+
+    #[inline(always)]
+    fn scl_output_signal(&self) -> OutputSignal {
+        OutputSignal::I2CEXT0_SCL
+    }
+
+    #[inline(always)]
+    fn scl_input_signal(&self) -> InputSignal {
+        InputSignal::I2CEXT0_SCL
+    }
+
+    #[inline(always)]
+    fn sda_output_signal(&self) -> OutputSignal {
+        OutputSignal::I2CEXT0_SDA
+    }
+
+    #[inline(always)]
+    fn sda_input_signal(&self) -> InputSignal {
+        InputSignal::I2CEXT0_SDA
+    }
 }
 
 #[cfg(not(esp32c3))]
@@ -969,4 +2245,24 @@ impl Instance for crate::pac::I2C1 {
     fn i2c_number(&self) -> usize {
         1
     }
+
+    #[inline(always)]
+    fn scl_output_signal(&self) -> OutputSignal {
+        OutputSignal::I2CEXT1_SCL
+    }
+
+    #[inline(always)]
+    fn scl_input_signal(&self) -> InputSignal {
+        InputSignal::I2CEXT1_SCL
+    }
+
+    #[inline(always)]
+    fn sda_output_signal(&self) -> OutputSignal {
+        OutputSignal::I2CEXT1_SDA
+    }
+
+    #[inline(always)]
+    fn sda_input_signal(&self) -> InputSignal {
+        InputSignal::I2CEXT1_SDA
+    }
 }
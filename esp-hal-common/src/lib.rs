@@ -46,6 +46,7 @@ pub use self::{
 pub mod analog;
 pub mod clock;
 pub mod delay;
+pub mod dma;
 pub mod gpio;
 pub mod i2c;
 pub mod ledc;
@@ -31,8 +31,65 @@
 //! * FIFO mode is not supported (there appear to be some issues with FIFO mode
 //!   in some variants and for consistency all variants therefore we use
 //!   NON-FIFO mode everywhere)
-//! * Non-blocking mode is currently not supported!
-//! * Input channels are currently not supported!
+//!
+//! ### Timing
+//! The lengths of a [`PulseCode`] are real durations. They are converted into
+//! ticks of the channel clock, which results from the clock source, the
+//! global divider (ESP32-C3 and ESP32-S3) and the channel divider. Lengths
+//! which do not fit into the 15 bit length field (or are shorter than one
+//! tick) are rejected with `TransmissionError::InvalidPulseLength`. The raw
+//! variants of the send and receive functions work with ticks directly.
+//!
+//! A carrier is configured from its frequency and duty cycle with
+//! [`OutputChannel::set_carrier`].
+//!
+//! ### Non-blocking transmissions
+//! [`ConfiguredChannel::start_send`] starts a transmission and returns right
+//! away. Its completion can be polled with [`ConfiguredChannel::is_done`],
+//! awaited with [`ConfiguredChannel::wait`] or signalled by an interrupt
+//! (see [`ConfiguredChannel::listen_tx_end`]). Transmissions in
+//! `RepeatMode::Forever` are ended with
+//! [`ConfiguredChannel::stop_transmission`].
+//!
+//! ### Synchronized transmissions
+//! Several channels can be combined into a [`SyncGroup`] to start them with
+//! a single call, after loading their sequences with
+//! [`ConfiguredChannel::load_pulse_sequence`]. The ESP32-C3 and ESP32-S3
+//! start them in the same clock cycle, the ESP32 and ESP32-S2 start them
+//! right after each other.
+//!
+//! ### Long sequences
+//! A channel can use the memory blocks of the following channels to hold
+//! longer sequences (see [`OutputChannel::set_memory_blocks`]). The following
+//! channels are consumed for this with `into_memory_block()`. Sequences
+//! which do not fit into the RAM at all are sent in wrap mode with
+//! [`ConfiguredChannel::start_wrapped_transmission`]. The RAM is then
+//! refilled in halves from an iterator of [`PulseCode`]s by calling
+//! [`ConfiguredChannel::refill`] from the RMT interrupt handler whenever the
+//! threshold interrupt of the channel is raised.
+//!
+//! ### Receiving
+//! Channels that can receive (all channels on the ESP32 and ESP32-S2,
+//! `Channel2`/`Channel3` on the ESP32-C3 and `Channel4`-`Channel7` on the
+//! ESP32-S3) are configured through the [`InputChannel`] trait. On the ESP32
+//! and ESP32-S2 a transmitter channel is turned into a receiver with
+//! `into_rx()`. Receiving starts with the first edge on the input pin and
+//! ends once the input stays idle for longer than the configured idle
+//! threshold. The received sequence is returned as [`PulseCode`] entries.
+//!
+//! ```
+//! let mut rmt_channel2 = pulse.channel2;
+//!
+//! rmt_channel2
+//!     .set_channel_divider(80)
+//!     .set_idle_threshold(10_000)
+//!     .set_filter_threshold(Some(100));
+//!
+//! let mut rmt_channel2 = rmt_channel2.assign_pin(io.pins.gpio9);
+//!
+//! let mut seq = [PulseCode::end_marker(); 48];
+//! let received = rmt_channel2.receive_pulse_sequence(&mut seq).unwrap();
+//! ```
 //!
 //! ### Example (for ESP32-C3)
 //! ```
@@ -46,6 +103,7 @@
 //!     0, // Integer part of the RMT-wide clock divider
 //!     0, // Numerator part of the RMT-wide clock divider
 //!     0, // Denominator part of the RMT-wide clock divider
+//!     &clocks,
 //! )
 //! .unwrap();
 //!
@@ -80,11 +138,17 @@
 
 use core::slice::Iter;
 
-use fugit::NanosDurationU32;
+use fugit::{HertzU32, NanosDurationU32};
 pub use paste::paste;
+use rmt_protocols::pulse_code::{nanos_to_ticks, pack, ticks_to_nanos, unpack};
 
 use crate::{
-    gpio::{types::OutputSignal, OutputPin},
+    clock::Clocks,
+    gpio::{
+        types::{InputSignal, OutputSignal},
+        InputPin,
+        OutputPin,
+    },
     pac::RMT,
     system::PeripheralClockControl,
 };
@@ -95,6 +159,24 @@ pub enum SetupError {
     /// The global configuration for the RMT peripheral is invalid
     /// (e.g. the fractional parameters are outOfBound)
     InvalidGlobalConfig,
+    /// The memory blocks to merge don't directly follow the channel or
+    /// exceed the memory blocks available to transmitter channels
+    InvalidMemoryBlocks,
+    /// The carrier frequency or duty cycle cannot be configured with the
+    /// clock of the channel
+    InvalidCarrierConfig,
+    /// A channel was added more than once to a [`SyncGroup`]
+    InvalidChannelGroup,
+}
+
+/// The memory block of a transmitter channel that was given up to be merged
+/// into the RAM of a preceding channel
+///
+/// Created by the `into_memory_block()` function of the channels, used with
+/// [`OutputChannel::set_memory_blocks`].
+#[derive(Debug)]
+pub struct MemoryBlock {
+    channel: u8,
 }
 
 /// Errors that can occur during a transmission attempt
@@ -105,9 +187,26 @@ pub enum TransmissionError {
     /// The maximum number of transmissions (`=(2^10)-1`) was exceeded
     RepetitionOverflow,
     /// The `RepeatNtimes` and `Forever` modesl are only feasible if the
-    /// sequence fits into the RAM in one go. If the sequence has > 48
-    /// elements, the `RepeatNtimes` and `Forever` modes cannot be used.
+    /// sequence fits into the RAM in one go. If the sequence has more
+    /// elements than the RAM of the channel (48 or 64 entries per memory
+    /// block), the `RepeatNtimes` and `Forever` modes cannot be used.
     IncompatibleRepeatMode,
+    /// The sequence does not fit into the RAM of the channel, which is
+    /// required for non-blocking transmissions
+    SequenceTooLong,
+    /// A pulse length does not fit into the 15 bit length field of a pulse
+    /// code with the clock configured for the channel (or is too short to be
+    /// represented by at least one clock tick)
+    InvalidPulseLength,
+}
+
+/// Errors that can occur during a reception attempt
+#[derive(Debug)]
+pub enum ReceiveError {
+    /// The received sequence did not fit into the RAM of the channel
+    MemoryOverflow,
+    /// The provided buffer is too small to hold the received sequence
+    BufferTooSmall,
 }
 
 /// Specifies the mode with which pulses are sent out in transmitter channels
@@ -152,6 +251,22 @@ const CHANNEL_RAM_SIZE: u8 = 64;
 #[cfg(any(esp32c3, esp32s3))]
 const CHANNEL_RAM_SIZE: u8 = 48;
 
+// Specifies how many channels (and thus memory blocks) can transmit. On the
+// ESP32-C3 and ESP32-S3 the following memory blocks belong to the receivers.
+#[cfg(any(esp32s3, esp32s2))]
+const TX_CHANNEL_COUNT: u8 = 4;
+#[cfg(esp32)]
+const TX_CHANNEL_COUNT: u8 = 8;
+#[cfg(esp32c3)]
+const TX_CHANNEL_COUNT: u8 = 2;
+
+// Specifies the bit enabling the synchronous start of channels in the TX_SIM
+// register (following the bits selecting the channels)
+#[cfg(esp32c3)]
+const TX_SIM_EN: u32 = 1 << 2;
+#[cfg(esp32s3)]
+const TX_SIM_EN: u32 = 1 << 4;
+
 // Specifies where the RMT RAM section starts for the particular ESP32 variant
 #[cfg(esp32s2)]
 const RMT_RAM_START: usize = 0x3f416400;
@@ -169,43 +284,54 @@ const RMT_RAM_START: usize = 0x60016800;
 pub struct PulseCode {
     /// Logical output level in the first pulse code interval
     pub level1: bool,
-    /// Length of the first pulse code interval (in clock cycles)
+    /// Length of the first pulse code interval
     pub length1: NanosDurationU32,
     /// Logical output level in the second pulse code interval
     pub level2: bool,
-    /// Length of the second pulse code interval (in clock cycles)
+    /// Length of the second pulse code interval
     pub length2: NanosDurationU32,
 }
 
-/// Convert a pulse code structure into a u32 value that can be written
-/// into the data registers
-impl From<PulseCode> for u32 {
-    #[inline(always)]
-    fn from(p: PulseCode) -> u32 {
-        // The Pulse Code format in the RAM appears to be
-        // little-endian
-
-        // The length1 value resides in bits [14:0]
-        let mut entry: u32 = p.length1.ticks() as u32;
-
-        // If level1 is high, set bit 15, otherwise clear it
-        if p.level1 {
-            entry |= 1 << 15;
-        } else {
-            entry &= !(1 << 15);
+impl PulseCode {
+    /// Create an end marker (both levels low and lengths of zero), which
+    /// ends a transmission
+    pub const fn end_marker() -> PulseCode {
+        PulseCode {
+            level1: false,
+            length1: NanosDurationU32::from_ticks(0),
+            level2: false,
+            length2: NanosDurationU32::from_ticks(0),
         }
+    }
 
-        // If level2 is high, set bit 31, otherwise clear it
-        if p.level2 {
-            entry |= 1 << 31;
-        } else {
-            entry &= !(1 << 31);
-        }
+    /// Convert the pulse code into a u32 value that can be written into the
+    /// data registers of a channel which counts in ticks of `tick_frequency`
+    pub fn to_raw(&self, tick_frequency: HertzU32) -> Result<u32, TransmissionError> {
+        let length1 = nanos_to_ticks(self.length1.ticks(), tick_frequency.raw())
+            .ok_or(TransmissionError::InvalidPulseLength)?;
+        let length2 = nanos_to_ticks(self.length2.ticks(), tick_frequency.raw())
+            .ok_or(TransmissionError::InvalidPulseLength)?;
+
+        Ok(pack(self.level1, length1, self.level2, length2))
+    }
+
+    /// Create a pulse code from a u32 value read from the data registers of a
+    /// channel which counts in ticks of `tick_frequency`
+    pub fn from_raw(entry: u32, tick_frequency: HertzU32) -> PulseCode {
+        let (level1, length1, level2, length2) = unpack(entry);
 
-        // The length2 value resides in bits [30:16]
-        entry |= (p.length2.ticks() as u32) << 16;
+        PulseCode {
+            level1,
+            length1: NanosDurationU32::from_ticks(ticks_to_nanos(length1, tick_frequency.raw())),
+            level2,
+            length2: NanosDurationU32::from_ticks(ticks_to_nanos(length2, tick_frequency.raw())),
+        }
+    }
+}
 
-        entry
+impl Default for PulseCode {
+    fn default() -> Self {
+        PulseCode::end_marker()
     }
 }
 
@@ -224,11 +350,36 @@ pub trait OutputChannel<CC> {
     /// Enable/Disable carrier modulation
     fn set_carrier_modulation(&mut self, state: bool) -> &mut Self;
 
+    /// Configure the carrier with its frequency and duty cycle (in percent)
+    ///
+    /// The carrier is added to the output while it is at `level`. Carrier
+    /// modulation still needs to be enabled with `set_carrier_modulation`.
+    fn set_carrier(
+        &mut self,
+        frequency: HertzU32,
+        duty_percent: u8,
+        level: bool,
+    ) -> Result<&mut Self, SetupError>;
+
     /// Set the clock source (for the ESP32-S2 abd ESP32 this can be done on a
     /// channel level)
     #[cfg(any(esp32s2, esp32))]
     fn set_clock_source(&mut self, source: ClockSource) -> &mut Self;
 
+    /// Merge the memory blocks of the following channels into the RAM of
+    /// this channel
+    ///
+    /// The blocks have to directly follow this channel in ascending order,
+    /// e.g. `channel0.set_memory_blocks([channel1.into_memory_block()])`.
+    /// The channels are consumed by this. If the blocks don't fit they are
+    /// returned together with the error. Only transmitter memory blocks can
+    /// be merged, so on the ESP32-C3 and ESP32-S3 the RAM of the receivers
+    /// stays untouched.
+    fn set_memory_blocks<const N: usize>(
+        &mut self,
+        blocks: [MemoryBlock; N],
+    ) -> Result<&mut Self, (SetupError, [MemoryBlock; N])>;
+
     /// Assign a pin that should be driven by this channel
     ///
     /// (Note that we only take a reference here, so the ownership remains with
@@ -258,11 +409,240 @@ pub trait ConfiguredChannel {
         sequence: &[u32; N],
     ) -> Result<(), TransmissionError>;
 
+    /// Start a transmission of an arbitrarily long sequence in wrap mode
+    ///
+    /// The RAM of the channel is filled from `codes` and the threshold
+    /// interrupt of the channel is enabled. Every time it is raised,
+    /// [`ConfiguredChannel::refill`] has to be called (usually from the RMT
+    /// interrupt handler) with the same iterator. An end marker is appended
+    /// once the iterator is exhausted, so an endless iterator (e.g. created
+    /// with `cycle()`) keeps the channel transmitting.
+    fn start_wrapped_transmission<I: Iterator<Item = PulseCode>>(
+        &mut self,
+        codes: &mut I,
+    ) -> Result<(), TransmissionError>;
+
+    /// Refill the half of the RAM which has already been sent
+    ///
+    /// Returns `false` once `codes` is exhausted, the threshold interrupt is
+    /// disabled then.
+    fn refill<I: Iterator<Item = PulseCode>>(
+        &mut self,
+        codes: &mut I,
+    ) -> Result<bool, TransmissionError>;
+
+    /// Start sending a pulse sequence without waiting for its completion
+    ///
+    /// The sequence has to fit into the RAM of the channel. Use
+    /// [`ConfiguredChannel::is_done`] or [`ConfiguredChannel::wait`] to check
+    /// for the completion of the transmission.
+    fn start_send<const N: usize>(
+        &mut self,
+        repeat_mode: RepeatMode,
+        sequence: &[PulseCode; N],
+    ) -> Result<(), TransmissionError>;
+
+    /// Load a pulse sequence into the RAM of the channel without starting
+    /// the transmission
+    ///
+    /// This is used to prepare the channels of a [`SyncGroup`] before they
+    /// are started together.
+    fn load_pulse_sequence<const N: usize>(
+        &mut self,
+        repeat_mode: RepeatMode,
+        sequence: &[PulseCode; N],
+    ) -> Result<(), TransmissionError>;
+
+    /// Check if the transmission started with [`ConfiguredChannel::start_send`]
+    /// has completed (successfully or with an error)
+    fn is_done(&self) -> bool;
+
+    /// Wait for the completion of the transmission started with
+    /// [`ConfiguredChannel::start_send`]
+    ///
+    /// This must not be used with `RepeatMode::Forever`, which only ends
+    /// when calling [`ConfiguredChannel::stop_transmission`].
+    fn wait(&mut self) -> Result<(), TransmissionError>;
+
+    /// Listen for end of transmission interrupts
+    fn listen_tx_end(&mut self);
+
+    /// Stop listening for end of transmission interrupts
+    fn unlisten_tx_end(&mut self);
+
+    /// Listen for threshold interrupts (raised whenever half of the RAM has
+    /// been sent)
+    fn listen_tx_threshold(&mut self);
+
+    /// Stop listening for threshold interrupts
+    fn unlisten_tx_threshold(&mut self);
+
+    /// Checks if the end of transmission interrupt is set
+    fn tx_end_interrupt_set(&self) -> bool;
+
+    /// Checks if the threshold interrupt is set
+    fn tx_threshold_interrupt_set(&self) -> bool;
+
+    /// Clear all interrupts of this channel
+    fn clear_interrupt(&mut self);
+
     /// Stop any ongoing (repetitive) transmission
     ///
     /// This function needs to be called to stop sending when
-    /// previously a sequence was sent with `RepeatMode::Forever`.
+    /// previously a sequence was sent with `RepeatMode::Forever`. The
+    /// interrupts of the channel stay enabled.
+    ///
+    /// The ESP32 has no stop bit, instead an end marker is written to the
+    /// start of the RAM of the channel and the read pointer is reset to it
+    /// (`mem_rd_rst`). This overwrites the first entry of the loaded sequence,
+    /// which needs to be loaded again before the next transmission.
     fn stop_transmission(&self);
+
+    /// Frequency of the clock ticks the channel counts pulse lengths in
+    fn tick_frequency(&self) -> HertzU32;
+}
+
+/// Functionality a channel needs to be started as part of a [`SyncGroup`]
+pub trait SyncChannel {
+    /// The number of the channel
+    fn channel_number(&self) -> u8;
+
+    /// Start the transmission of the sequence loaded with
+    /// [`ConfiguredChannel::load_pulse_sequence`]
+    fn start_loaded(&mut self);
+
+    /// Check if the transmission of the channel has completed
+    fn finished(&self) -> bool;
+}
+
+/// Group of transmitter channels which are started at the same time
+///
+/// The ESP32-C3 and ESP32-S3 start the channels of a group in the same clock
+/// cycle (using the `TX_SIM` register), their channel clocks are reset
+/// beforehand to keep the channels aligned. The ESP32 and ESP32-S2 lack this
+/// functionality, the channels are started one after another within a
+/// critical section there, which leaves them a few clock cycles apart.
+///
+/// The group only borrows the channels, it can be dropped after starting
+/// to load the next sequences into the channels.
+pub struct SyncGroup<'a, const N: usize> {
+    channels: [&'a mut dyn SyncChannel; N],
+}
+
+impl<'a, const N: usize> SyncGroup<'a, N> {
+    /// Create a new group out of the given channels
+    pub fn new(channels: [&'a mut dyn SyncChannel; N]) -> Result<Self, SetupError> {
+        let group = SyncGroup { channels };
+
+        if group.channel_mask().count_ones() as usize != N {
+            return Err(SetupError::InvalidChannelGroup);
+        }
+
+        Ok(group)
+    }
+
+    /// Start the transmissions of all channels of the group
+    ///
+    /// The sequences have to be loaded with
+    /// [`ConfiguredChannel::load_pulse_sequence`] before.
+    pub fn start(&mut self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(esp32c3, esp32s3))] {
+                let mask = self.channel_mask();
+                let rmt = unsafe { &*RMT::PTR };
+
+                // Select the channels which have to wait for each other
+                rmt.tx_sim.write(|w| unsafe { w.bits(mask | TX_SIM_EN) });
+
+                // Reset the clock dividers to align the channel clocks
+                rmt.ref_cnt_rst.write(|w| unsafe { w.bits(mask) });
+
+                // The transmissions start once all channels have been started
+                for channel in self.channels.iter_mut() {
+                    channel.start_loaded();
+                }
+
+                // Allow the channels to be started individually again
+                rmt.tx_sim.write(|w| unsafe { w.bits(0) });
+            } else {
+                critical_section::with(|_| {
+                    for channel in self.channels.iter_mut() {
+                        channel.start_loaded();
+                    }
+                });
+            }
+        }
+    }
+
+    /// Check if the transmissions of all channels of the group have completed
+    pub fn is_done(&self) -> bool {
+        self.channels.iter().all(|channel| channel.finished())
+    }
+
+    fn channel_mask(&self) -> u32 {
+        self.channels
+            .iter()
+            .fold(0, |mask, channel| mask | 1 << channel.channel_number())
+    }
+}
+
+/// Functionality that every InputChannel must support
+pub trait InputChannel<CC> {
+    /// Set the number of (divided) clock cycles without an edge after which
+    /// the reception of a sequence is considered finished
+    fn set_idle_threshold(&mut self, threshold: u16) -> &mut Self;
+
+    /// Set the glitch filter threshold, pulses shorter than `threshold`
+    /// source clock cycles are ignored (`None` disables the filter)
+    fn set_filter_threshold(&mut self, threshold: Option<u8>) -> &mut Self;
+
+    /// Set channel clock divider value
+    fn set_channel_divider(&mut self, divider: u8) -> &mut Self;
+
+    /// Enable/Disable carrier demodulation
+    #[cfg(not(esp32))]
+    fn set_carrier_demodulation(&mut self, state: bool) -> &mut Self;
+
+    /// Set the high and low thresholds (in divided clock cycles) of the
+    /// carrier that should be removed from the received signal
+    #[cfg(not(esp32))]
+    fn set_carrier_thresholds(&mut self, high: u16, low: u16) -> &mut Self;
+
+    /// Set the level on which the carrier is present in the received signal
+    #[cfg(not(esp32))]
+    fn set_carrier_level(&mut self, level: bool) -> &mut Self;
+
+    /// Set the clock source (for the ESP32-S2 and ESP32 this can be done on a
+    /// channel level)
+    #[cfg(any(esp32s2, esp32))]
+    fn set_clock_source(&mut self, source: ClockSource) -> &mut Self;
+
+    /// Assign a pin that this channel should receive pulses from
+    fn assign_pin<RmtPin: InputPin>(self, pin: RmtPin) -> CC;
+}
+
+/// Functionality that is allowed only on configured input channels
+pub trait ConfiguredInputChannel {
+    /// Receive a pulse sequence in a blocking fashion
+    ///
+    /// Returns the number of pulse codes written into `buffer`. The last
+    /// entry may have a `length2` of zero, which marks the end of the
+    /// sequence.
+    fn receive_pulse_sequence(&mut self, buffer: &mut [PulseCode]) -> Result<usize, ReceiveError>;
+
+    /// Receive a raw pulse sequence in a blocking fashion
+    ///
+    /// The `buffer` elements are filled in the u32 format used by the RMT.
+    /// Please refer to the reference manual or use the variant which
+    /// returns `PulseCode` objects instead.
+    fn receive_pulse_sequence_raw(&mut self, buffer: &mut [u32]) -> Result<usize, ReceiveError>;
+
+    /// Stop any ongoing reception and hand the channel RAM back to the
+    /// software
+    fn stop_reception(&self);
+
+    /// Frequency of the clock ticks the channel counts pulse lengths in
+    fn tick_frequency(&self) -> HertzU32;
 }
 
 macro_rules! channel_instance {
@@ -271,11 +651,16 @@ macro_rules! channel_instance {
         /// RX/TX Input/Output Channel
         pub struct $cxi {
             mem_offset: usize,
+            mem_blocks: u8,
+            sclk: HertzU32,
         }
         impl $cxi {
             /// Create a new channel instance
-            pub fn new() -> Self {
-                let mut channel = $cxi { mem_offset: 0 };
+            ///
+            /// `sclk` is the frequency of the RMT clock (after the global
+            /// divider on the ESP32-C3 and ESP32-S3).
+            pub fn new(sclk: HertzU32) -> Self {
+                let mut channel = $cxi { mem_offset: 0, mem_blocks: 1, sclk };
 
                 cfg_if::cfg_if! {
                     if #[cfg(any(esp32c3, esp32s3))] {
@@ -319,12 +704,64 @@ macro_rules! channel_instance {
                 channel
             }
 
+            /// Give up this channel to merge its memory block into the RAM
+            /// of the preceding channel
+            ///
+            /// See [`OutputChannel::set_memory_blocks`].
+            pub fn into_memory_block(self) -> MemoryBlock {
+                MemoryBlock { channel: $num }
+            }
+
+            /// Frequency of the clock the channel divider is applied to
+            pub fn source_frequency(&self) -> HertzU32 {
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32, esp32s2))] {
+                        // The REF_TICK is always configured to 1 MHz
+                        if conf1!($num).read().ref_always_on().bit_is_set() {
+                            self.sclk
+                        } else {
+                            HertzU32::MHz(1)
+                        }
+                    } else {
+                        self.sclk
+                    }
+                }
+            }
+
+            /// Frequency of the clock ticks the channel counts pulse lengths in
+            pub fn tick_frequency(&self) -> HertzU32 {
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32c3, esp32s3))] {
+                        let divider = unsafe { &*RMT::PTR }
+                            .ch_tx_conf0[$num]
+                            .read()
+                            .div_cnt()
+                            .bits();
+                    } else {
+                        let divider = conf0!($num).read().div_cnt().bits();
+                    }
+                }
+
+                // A divider value of 0 divides by 256
+                match divider {
+                    0 => self.source_frequency() / 256,
+                    divider => self.source_frequency() / divider as u32,
+                }
+            }
+
+            /// Number of pulse code entries available in the RAM of this
+            /// channel (including merged memory blocks)
+            #[inline(always)]
+            fn ram_size(&self) -> usize {
+                CHANNEL_RAM_SIZE as usize * self.mem_blocks as usize
+            }
+
             /// Write a sequence of pulse codes into the RMT fifo buffer
             #[inline(always)]
             fn write_sequence(
                 &mut self,
                 seq_iter: &mut Iter<u32>,
-                max_inserted_elements: u8,
+                max_inserted_elements: usize,
             ){
                 for _ in 0..max_inserted_elements {
                     match seq_iter.next() {
@@ -336,6 +773,36 @@ macro_rules! channel_instance {
                 }
             }
 
+            /// Write pulse codes taken from an iterator into the RMT fifo buffer
+            ///
+            /// Once the iterator is exhausted, an end marker is written and
+            /// `false` is returned.
+            #[inline(always)]
+            fn write_codes<I: Iterator<Item = PulseCode>>(
+                &mut self,
+                codes: &mut I,
+                max_inserted_elements: usize,
+            ) -> Result<bool, TransmissionError> {
+                let tick_frequency = self.tick_frequency();
+
+                for _ in 0..max_inserted_elements {
+                    match codes.next().map(|pulse| pulse.to_raw(tick_frequency)) {
+                        None => {
+                            // A zero length entry marks the end of the transmission
+                            self.load_fifo(0);
+                            return Ok(false);
+                        }
+                        Some(Ok(pulse)) => self.load_fifo(pulse),
+                        Some(Err(error)) => {
+                            // Terminate the transmission at the invalid pulse code
+                            self.load_fifo(0);
+                            return Err(error);
+                        }
+                    }
+                }
+                Ok(true)
+            }
+
             #[inline(always)]
             fn load_fifo(&mut self, value: u32) {
                 let base_ptr: usize = RMT_RAM_START + ($num * CHANNEL_RAM_SIZE as usize * 4);
@@ -345,7 +812,7 @@ macro_rules! channel_instance {
                 }
 
                 self.mem_offset += 4;
-                if self.mem_offset >= CHANNEL_RAM_SIZE as usize * 4 {
+                if self.mem_offset >= self.ram_size() * 4 {
                     self.mem_offset = 0;
                 }
             }
@@ -354,59 +821,10 @@ macro_rules! channel_instance {
             fn reset_fifo(&mut self) {
                 self.mem_offset = 0;
             }
-        }
-
-        paste!(
-            #[doc = "Wrapper for`" $cxi "` object."]
-            pub struct [<Configured $cxi>] {
-                channel: $cxi,
-            }
-
-            impl ConfiguredChannel for [<Configured $cxi>] {
-                /// Send a pulse sequence in a blocking fashion
-                fn send_pulse_sequence<const N: usize>(
-                    &mut self,
-                    repeat_mode: RepeatMode,
-                    sequence: &[PulseCode; N],
-                ) -> Result<(), TransmissionError> {
-                    let precomputed_sequence = sequence.map(|x| u32::from(x));
-
-                    self.send_pulse_sequence_raw(repeat_mode, &precomputed_sequence)
-                }
-
-            /// Send a raw pulse sequence in a blocking fashion
-            ///
-            /// In this function we expect the `sequence` elements to be already
-            /// in the correct u32 format that is understood by the RMT.
-            /// Please refer to the reference manual or use the variant which
-            /// accepts `PulseCode` objects instead.
-            ///
-            /// We expect that the end marker is already part of the provided
-            /// sequence and to be provided in all modes!
-            fn send_pulse_sequence_raw<const N: usize>(
-                &mut self,
-                repeat_mode: RepeatMode,
-                sequence: &[u32; N],
-            ) -> Result<(), TransmissionError> {
-                // Check for any configuration error states
-                match repeat_mode {
-                    #[cfg(not(esp32))]
-                    RepeatMode::RepeatNtimes(val) => {
-                        if val >= 1024 {
-                            return Err(TransmissionError::RepetitionOverflow);
-                        }
-                        if sequence.len() > CHANNEL_RAM_SIZE as usize {
-                            return Err(TransmissionError::IncompatibleRepeatMode);
-                        }
-                    }
-                    RepeatMode::Forever => {
-                        if sequence.len() > CHANNEL_RAM_SIZE as usize {
-                            return Err(TransmissionError::IncompatibleRepeatMode);
-                        }
-                    }
-                    _ => (),
-                };
 
+            /// Configure the repetitions and the refill threshold and reset
+            /// the RAM pointers before a new transmission
+            fn prepare_transmission(&mut self, repeat_mode: RepeatMode) {
                 // Depending on the variant, other registers have to be used here
                 cfg_if::cfg_if! {
                     if #[cfg(any(esp32, esp32s2))] {
@@ -416,16 +834,19 @@ macro_rules! channel_instance {
                     }
                 }
 
+                // The refill threshold is half the size of the RAM in case we
+                // use wrap mode
+                let threshold = (self.ram_size() / 2) as u16;
+
                 // The ESP32 does not support loop/count modes, as such we have to
                 // only configure a subset of registers
                 cfg_if::cfg_if! {
                     if #[cfg(esp32)] {
                         // Configure counting mode and repetitions
                         unsafe { &*RMT::PTR }.ch_tx_lim[$num].modify(|_, w| unsafe {
-                            // Set the interrupt threshold for sent pulse codes to
-                            // half the size of the RAM in case we use wrap mode
+                            // Set the interrupt threshold for sent pulse codes
                             w.tx_lim()
-                                .bits(CHANNEL_RAM_SIZE as u16 /2)
+                                .bits(threshold)
                         });
                     } else {
                         // Extract repetition value
@@ -445,10 +866,9 @@ macro_rules! channel_instance {
                                 // Reset any pre-existing counting value
                                 .loop_count_reset()
                                 .set_bit()
-                                // Set the interrupt threshold for sent pulse codes to 24
-                                // (= half the size of the RAM) in case we use wrap mode
+                                // Set the interrupt threshold for sent pulse codes
                                 .tx_lim()
-                                .bits(CHANNEL_RAM_SIZE as u16/2)
+                                .bits(threshold)
                         });
                     }
                 }
@@ -471,20 +891,12 @@ macro_rules! channel_instance {
                         .set_bit()
                 });
 
-                self.channel.reset_fifo();
-                let mut sequence_iter = sequence.iter();
-
-                // We have to differentiate here if we can fit the whole sequence
-                // in the RAM in one go or if we have to use the wrap mode to split
-                // the sequence into chuncks.
-                if sequence.len() >= CHANNEL_RAM_SIZE as usize {
-                    // Write the first 48 entries
-                    self.channel.write_sequence(&mut sequence_iter, CHANNEL_RAM_SIZE);
-                } else {
-                    // Write whole sequence to FIFO RAM
-                    self.channel.write_sequence(&mut sequence_iter, CHANNEL_RAM_SIZE);
-                }
+                self.reset_fifo();
+            }
 
+            /// Clear the interrupts of this channel
+            #[inline(always)]
+            fn clear_interrupts(&self) {
                 // Clear the relevant interrupts
                 //
                 // (since this is a write-through register, we can do this
@@ -532,6 +944,35 @@ macro_rules! channel_instance {
                         });
                     }
                 }
+            }
+
+            /// Read the raw interrupt states of this channel
+            ///
+            /// Returns the states of the end, loop, error and threshold
+            /// interrupts (in that order)
+            #[inline(always)]
+            fn tx_interrupts(&self) -> (bool, bool, bool, bool) {
+                let interrupts = unsafe { &*RMT::PTR }.int_raw.read();
+
+                (
+                    unsafe { interrupts.ch_tx_end_int_raw($num).bit() },
+                    // The ESP32 variant does not support the loop functionality
+                    #[cfg(not(esp32))]
+                    unsafe {interrupts.ch_tx_loop_int_raw($num).bit()},
+                    #[cfg(esp32)]
+                    false,
+                    // The C3/S3 have a slightly different interrupt naming scheme
+                    #[cfg(any(esp32, feature= "esp32s2"))]
+                    unsafe { interrupts.ch_err_int_raw($num).bit() },
+                    #[cfg(any(esp32c3, feature= "esp32s3"))]
+                    unsafe { interrupts.ch_tx_err_int_raw($num).bit() },
+                    unsafe { interrupts.ch_tx_thr_event_int_raw($num).bit() },
+                )
+            }
+
+            /// Clear the interrupts of this channel and start the transmission
+            fn start_transmission(&mut self) {
+                self.clear_interrupts();
 
                 // always enable tx wrap
                 #[cfg(any(esp32c3, esp32s3))]
@@ -555,71 +996,295 @@ macro_rules! channel_instance {
                         unsafe{ &*RMT::PTR }.ch_tx_conf0[$num].modify(|_, w| w.tx_start().set_bit());
                     }
                 }
+            }
 
-                // If we're in forever mode, we return right away, otherwise we wait
-                // for completion
-                if repeat_mode != RepeatMode::Forever {
-                    // Wait for interrupt being raised, either completion or error
-                    loop {
-                        let interrupts = unsafe { &*RMT::PTR }.int_raw.read();
+            /// Enable/Disable the threshold interrupt used for refilling the RAM
+            fn enable_threshold_interrupt(&mut self, state: bool) {
+                unsafe { &*RMT::PTR }.int_ena.modify(|_, w| {
+                    paste!(w.[<ch $num _tx_thr_event_int_ena>]().bit(state))
+                });
+            }
 
-                        match (
-                            unsafe { interrupts.ch_tx_end_int_raw($num).bit() },
-                            // The ESP32 variant does not support the loop functionality
-                            #[cfg(not(esp32))]
-                            unsafe {interrupts.ch_tx_loop_int_raw($num).bit()},
-                            #[cfg(esp32)]
-                            false,
-                            // The C3/S3 have a slightly different interrupt naming scheme
-                            #[cfg(any(esp32, feature= "esp32s2"))]
-                            unsafe { interrupts.ch_err_int_raw($num).bit() },
-                            #[cfg(any(esp32c3, feature= "esp32s3"))]
-                            unsafe { interrupts.ch_tx_err_int_raw($num).bit() },
-                            unsafe { interrupts.ch_tx_thr_event_int_raw($num).bit() },
-                        ) {
-                            // SingleShot completed and no error -> success
-                            (true, false, false, _) => break,
-                            // Sequence completed and no error -> success
-                            (false, true, false, _) => {
-                                // Stop transmitting (only necessary in sequence case)
-                                self.stop_transmission();
-                                break;
-                            }
-                            // Refill the buffer
-                            (false, false, false, true) => {
-                                self.channel.write_sequence(&mut sequence_iter, CHANNEL_RAM_SIZE / 2);
+            /// Enable/Disable the end of transmission interrupt
+            fn enable_end_interrupt(&mut self, state: bool) {
+                unsafe { &*RMT::PTR }.int_ena.modify(|_, w| {
+                    paste!(w.[<ch $num _tx_end_int_ena>]().bit(state))
+                });
+            }
+        }
 
-                                // Clear the threshold interrupt (write-through)
-                                unsafe { &*RMT::PTR }.int_clr.write(|w| {
-                                    paste!(w.[<ch $num _tx_thr_event_int_clr>]().set_bit())
-                                });
-                        }
-                            // Neither completed nor error -> continue busy waiting
-                            (false, false, false, false) => (),
-                            // Anything else constitutes an error state
-                            _ => {
-                                return Err(TransmissionError::Failure(
-                                    unsafe { interrupts.ch_tx_end_int_raw($num).bit() },
-                                    // The ESP32 variant does not support the loop functionality
-                                    #[cfg(not(esp32))]
-                                    unsafe {interrupts.ch_tx_loop_int_raw($num).bit()},
-                                    #[cfg(esp32)]
-                                    false,
-                                    // The C3/S3 have a slightly different interrupt naming scheme
-                                    #[cfg(any(esp32, feature= "esp32s2"))]
-                                    unsafe { interrupts.ch_err_int_raw($num).bit() },
-                                    #[cfg(any(esp32c3, feature= "esp32s3"))]
-                                    unsafe { interrupts.ch_tx_err_int_raw($num).bit() },
-                                    unsafe { interrupts.ch_tx_thr_event_int_raw($num).bit() },
-                                ))
-                            }
-                        }
+        paste!(
+            #[doc = "Wrapper for`" $cxi "` object."]
+            pub struct [<Configured $cxi>] {
+                channel: $cxi,
+            }
+
+            impl ConfiguredChannel for [<Configured $cxi>] {
+                /// Send a pulse sequence in a blocking fashion
+                fn send_pulse_sequence<const N: usize>(
+                    &mut self,
+                    repeat_mode: RepeatMode,
+                    sequence: &[PulseCode; N],
+                ) -> Result<(), TransmissionError> {
+                    let tick_frequency = self.channel.tick_frequency();
+
+                    let mut precomputed_sequence = [0u32; N];
+                    for (entry, pulse) in precomputed_sequence.iter_mut().zip(sequence.iter()) {
+                        *entry = pulse.to_raw(tick_frequency)?;
+                    }
+
+                    self.send_pulse_sequence_raw(repeat_mode, &precomputed_sequence)
+                }
+
+            /// Send a raw pulse sequence in a blocking fashion
+            ///
+            /// In this function we expect the `sequence` elements to be already
+            /// in the correct u32 format that is understood by the RMT.
+            /// Please refer to the reference manual or use the variant which
+            /// accepts `PulseCode` objects instead.
+            ///
+            /// We expect that the end marker is already part of the provided
+            /// sequence and to be provided in all modes!
+            fn send_pulse_sequence_raw<const N: usize>(
+                &mut self,
+                repeat_mode: RepeatMode,
+                sequence: &[u32; N],
+            ) -> Result<(), TransmissionError> {
+                let ram_size = self.channel.ram_size();
+
+                // Check for any configuration error states
+                match repeat_mode {
+                    #[cfg(not(esp32))]
+                    RepeatMode::RepeatNtimes(val) => {
+                        if val >= 1024 {
+                            return Err(TransmissionError::RepetitionOverflow);
+                        }
+                        if sequence.len() > ram_size {
+                            return Err(TransmissionError::IncompatibleRepeatMode);
+                        }
+                    }
+                    RepeatMode::Forever => {
+                        if sequence.len() > ram_size {
+                            return Err(TransmissionError::IncompatibleRepeatMode);
+                        }
+                    }
+                    _ => (),
+                };
+
+                self.channel.prepare_transmission(repeat_mode);
+
+                // Fill the whole RAM, if the sequence is longer the remaining
+                // entries are written in chunks of half the RAM size (wrap mode)
+                let mut sequence_iter = sequence.iter();
+                self.channel.write_sequence(&mut sequence_iter, ram_size);
+
+                self.channel.start_transmission();
+
+                // If we're in forever mode, we return right away, otherwise we wait
+                // for completion
+                if repeat_mode != RepeatMode::Forever {
+                    // Wait for interrupt being raised, either completion or error
+                    loop {
+                        match self.channel.tx_interrupts() {
+                            // SingleShot completed and no error -> success
+                            (true, false, false, _) => break,
+                            // Sequence completed and no error -> success
+                            (false, true, false, _) => {
+                                // Stop transmitting (only necessary in sequence case)
+                                self.stop_transmission();
+                                break;
+                            }
+                            // Refill the buffer
+                            (false, false, false, true) => {
+                                self.channel.write_sequence(&mut sequence_iter, ram_size / 2);
+
+                                // Clear the threshold interrupt (write-through)
+                                unsafe { &*RMT::PTR }.int_clr.write(|w| {
+                                    paste!(w.[<ch $num _tx_thr_event_int_clr>]().set_bit())
+                                });
+                        }
+                            // Neither completed nor error -> continue busy waiting
+                            (false, false, false, false) => (),
+                            // Anything else constitutes an error state
+                            (end, looped, error, threshold) => {
+                                return Err(TransmissionError::Failure(
+                                    end, looped, error, threshold,
+                                ))
+                            }
+                        }
                     }
                 }
 
                 Ok(())
             }
 
+            /// Start a transmission of an arbitrarily long sequence in wrap mode
+            ///
+            /// The RAM of the channel is filled from `codes` and the threshold
+            /// interrupt of the channel is enabled. Every time it is raised,
+            /// [`ConfiguredChannel::refill`] has to be called (usually from the
+            /// RMT interrupt handler) with the same iterator. An end marker is
+            /// appended once the iterator is exhausted, so an endless iterator
+            /// (e.g. created with `cycle()`) keeps the channel transmitting.
+            fn start_wrapped_transmission<I: Iterator<Item = PulseCode>>(
+                &mut self,
+                codes: &mut I,
+            ) -> Result<(), TransmissionError> {
+                let ram_size = self.channel.ram_size();
+
+                self.channel.prepare_transmission(RepeatMode::SingleShot);
+
+                if self.channel.write_codes(codes, ram_size)? {
+                    self.channel.enable_threshold_interrupt(true);
+                }
+
+                self.channel.start_transmission();
+
+                Ok(())
+            }
+
+            /// Refill the half of the RAM which has already been sent
+            ///
+            /// This function needs to be called whenever the threshold interrupt
+            /// of a transmission started with
+            /// [`ConfiguredChannel::start_wrapped_transmission`] is raised. It
+            /// returns `false` once `codes` is exhausted, the threshold
+            /// interrupt is disabled then.
+            fn refill<I: Iterator<Item = PulseCode>>(
+                &mut self,
+                codes: &mut I,
+            ) -> Result<bool, TransmissionError> {
+                let result = self.channel.write_codes(codes, self.channel.ram_size() / 2);
+
+                if !matches!(result, Ok(true)) {
+                    self.channel.enable_threshold_interrupt(false);
+                }
+
+                // Clear the threshold interrupt (write-through)
+                unsafe { &*RMT::PTR }.int_clr.write(|w| {
+                    paste!(w.[<ch $num _tx_thr_event_int_clr>]().set_bit())
+                });
+
+                result
+            }
+
+            /// Start sending a pulse sequence without waiting for its completion
+            ///
+            /// The sequence has to fit into the RAM of the channel. Use
+            /// [`ConfiguredChannel::is_done`] or [`ConfiguredChannel::wait`] to check
+            /// for the completion of the transmission.
+            fn start_send<const N: usize>(
+                &mut self,
+                repeat_mode: RepeatMode,
+                sequence: &[PulseCode; N],
+            ) -> Result<(), TransmissionError> {
+                self.load_pulse_sequence(repeat_mode, sequence)?;
+                self.channel.start_transmission();
+
+                Ok(())
+            }
+
+            /// Load a pulse sequence into the RAM of the channel without starting
+            /// the transmission
+            fn load_pulse_sequence<const N: usize>(
+                &mut self,
+                repeat_mode: RepeatMode,
+                sequence: &[PulseCode; N],
+            ) -> Result<(), TransmissionError> {
+                if sequence.len() > self.channel.ram_size() {
+                    return Err(TransmissionError::SequenceTooLong);
+                }
+
+                #[cfg(not(esp32))]
+                if let RepeatMode::RepeatNtimes(val) = repeat_mode {
+                    if val >= 1024 {
+                        return Err(TransmissionError::RepetitionOverflow);
+                    }
+                }
+
+                let tick_frequency = self.channel.tick_frequency();
+
+                let mut precomputed_sequence = [0u32; N];
+                for (entry, pulse) in precomputed_sequence.iter_mut().zip(sequence.iter()) {
+                    *entry = pulse.to_raw(tick_frequency)?;
+                }
+
+                self.channel.prepare_transmission(repeat_mode);
+                self.channel
+                    .write_sequence(&mut precomputed_sequence.iter(), self.channel.ram_size());
+
+                Ok(())
+            }
+
+            /// Check if the transmission started with [`ConfiguredChannel::start_send`]
+            /// has completed (successfully or with an error)
+            fn is_done(&self) -> bool {
+                let (end, looped, error, _) = self.channel.tx_interrupts();
+                end || looped || error
+            }
+
+            /// Wait for the completion of the transmission started with
+            /// [`ConfiguredChannel::start_send`]
+            ///
+            /// This must not be used with `RepeatMode::Forever`, which only ends
+            /// when calling [`ConfiguredChannel::stop_transmission`].
+            fn wait(&mut self) -> Result<(), TransmissionError> {
+                loop {
+                    match self.channel.tx_interrupts() {
+                        // SingleShot completed and no error -> success
+                        (true, false, false, _) => return Ok(()),
+                        // Sequence completed and no error -> success
+                        (false, true, false, _) => {
+                            // Stop transmitting (only necessary in sequence case)
+                            self.stop_transmission();
+                            return Ok(());
+                        }
+                        // Neither completed nor error -> continue busy waiting
+                        (false, false, false, _) => (),
+                        // Anything else constitutes an error state
+                        (end, looped, error, threshold) => {
+                            return Err(TransmissionError::Failure(end, looped, error, threshold))
+                        }
+                    }
+                }
+            }
+
+            /// Listen for end of transmission interrupts
+            fn listen_tx_end(&mut self) {
+                self.channel.enable_end_interrupt(true);
+            }
+
+            /// Stop listening for end of transmission interrupts
+            fn unlisten_tx_end(&mut self) {
+                self.channel.enable_end_interrupt(false);
+            }
+
+            /// Listen for threshold interrupts (raised whenever half of the RAM has
+            /// been sent)
+            fn listen_tx_threshold(&mut self) {
+                self.channel.enable_threshold_interrupt(true);
+            }
+
+            /// Stop listening for threshold interrupts
+            fn unlisten_tx_threshold(&mut self) {
+                self.channel.enable_threshold_interrupt(false);
+            }
+
+            /// Checks if the end of transmission interrupt is set
+            fn tx_end_interrupt_set(&self) -> bool {
+                self.channel.tx_interrupts().0
+            }
+
+            /// Checks if the threshold interrupt is set
+            fn tx_threshold_interrupt_set(&self) -> bool {
+                self.channel.tx_interrupts().3
+            }
+
+            /// Clear all interrupts of this channel
+            fn clear_interrupt(&mut self) {
+                self.channel.clear_interrupts();
+            }
+
             /// Stop any ongoing (repetitive) transmission
             ///
             /// This function needs to be called to stop sending when
@@ -635,10 +1300,51 @@ macro_rules! channel_instance {
                         conf1!($num)
                             .modify(|_, w| w.tx_stop().set_bit());
                     }
-                    // The ESP32 variant does not have any way to stop a
-                    // transmission once it has been started!
+                    else {
+                        // The ESP32 variant does not have a stop bit, instead an
+                        // end marker is placed at the start of the RAM and the
+                        // read pointer is reset to it
+                        let ram_ptr =
+                            (RMT_RAM_START + ($num * CHANNEL_RAM_SIZE as usize * 4)) as *mut u32;
+                        unsafe {
+                            ram_ptr.write_volatile(0);
+                        }
+
+                        conf1!($num).modify(|_, w| {
+                            w.tx_conti_mode()
+                                .clear_bit()
+                                .tx_start()
+                                .clear_bit()
+                                .mem_rd_rst()
+                                .set_bit()
+                        });
+                        conf1!($num).modify(|_, w| w.mem_rd_rst().clear_bit());
+                    }
                 };
             }
+
+            /// Frequency of the clock ticks the channel counts pulse lengths in
+            fn tick_frequency(&self) -> HertzU32 {
+                self.channel.tick_frequency()
+            }
+            }
+
+            impl SyncChannel for [<Configured $cxi>] {
+                /// The number of the channel
+                fn channel_number(&self) -> u8 {
+                    $num
+                }
+
+                /// Start the transmission of the sequence loaded with
+                /// [`ConfiguredChannel::load_pulse_sequence`]
+                fn start_loaded(&mut self) {
+                    self.channel.start_transmission();
+                }
+
+                /// Check if the transmission of the channel has completed
+                fn finished(&self) -> bool {
+                    self.is_done()
+                }
             }
 
         );
@@ -735,6 +1441,83 @@ macro_rules! output_channel {
                 self
             }
 
+            /// Merge the memory blocks of the following channels into the RAM
+            /// of this channel
+            fn set_memory_blocks<const N: usize>(
+                &mut self,
+                blocks: [MemoryBlock; N],
+            ) -> Result<&mut Self, (SetupError, [MemoryBlock; N])> {
+                let following = blocks
+                    .iter()
+                    .enumerate()
+                    .all(|(index, block)| block.channel as usize == $num + 1 + index);
+
+                // Receivers don't hand out their memory blocks, the bound only
+                // guards against overlapping their RAM
+                if !following || $num + 1 + N > TX_CHANNEL_COUNT as usize {
+                    return Err((SetupError::InvalidMemoryBlocks, blocks));
+                }
+
+                let blocks = N as u8 + 1;
+
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32c3, esp32s3))] {
+                        unsafe { &*RMT::PTR }
+                            .ch_tx_conf0[$num]
+                            .modify(|_, w| unsafe { w.mem_size().bits(blocks) });
+                    }
+                    else {
+                        conf0!($num)
+                            .modify(|_, w| unsafe { w.mem_size().bits(blocks) });
+                    }
+                };
+
+                self.mem_blocks = blocks;
+                self.reset_fifo();
+                Ok(self)
+            }
+
+            /// Configure the carrier with its frequency and duty cycle (in percent)
+            fn set_carrier(
+                &mut self,
+                frequency: HertzU32,
+                duty_percent: u8,
+                level: bool,
+            ) -> Result<&mut Self, SetupError> {
+                if frequency.raw() == 0 || duty_percent == 0 || duty_percent >= 100 {
+                    return Err(SetupError::InvalidCarrierConfig);
+                }
+
+                // The carrier is generated from the undivided clock of the channel
+                let period = self.source_frequency().raw() / frequency.raw();
+                let high = period * duty_percent as u32 / 100;
+                let low = period - high;
+
+                if high == 0 || low == 0 || high > u16::MAX as u32 || low > u16::MAX as u32 {
+                    return Err(SetupError::InvalidCarrierConfig);
+                }
+
+                carrier_duty!($num).write(|w| unsafe {
+                    w.carrier_high()
+                        .bits(high as u16)
+                        .carrier_low()
+                        .bits(low as u16)
+                });
+
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32c3, esp32s3))] {
+                        unsafe { &*RMT::PTR }
+                            .ch_tx_conf0[$num]
+                            .modify(|_, w| w.carrier_out_lv().bit(level).conf_update().set_bit());
+                    }
+                    else {
+                        conf0!($num)
+                            .modify(|_, w| w.carrier_out_lv().bit(level));
+                    }
+                };
+                Ok(self)
+            }
+
             /// Assign a pin that should be driven by this channel
             fn assign_pin<RmtPin: OutputPin >(
                 self,
@@ -753,6 +1536,453 @@ macro_rules! output_channel {
     };
 }
 
+macro_rules! input_channel {
+    ($num:literal, $cxi:ident, $input_signal:path
+        ) => {
+        /// RX Input Channel
+        pub struct $cxi {
+            sclk: HertzU32,
+        }
+
+        impl $cxi {
+            /// Create a new input channel instance
+            ///
+            /// `sclk` is the frequency of the RMT clock (after the global
+            /// divider on the ESP32-C3 and ESP32-S3).
+            pub fn new(sclk: HertzU32) -> Self {
+                let mut channel = $cxi { sclk };
+
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32c3, esp32s3))] {
+                        // Apply default configuration
+                        rx_conf0!($num).modify(|_, w| unsafe {
+                            // Configure memory block size
+                            w.mem_size()
+                                .bits(1)
+                        });
+                        rx_conf1!($num).modify(|_, w|
+                            // The software owns the RAM until a reception is started
+                            w.mem_owner()
+                                .clear_bit()
+                                // Disable wrap mode
+                                .mem_rx_wrap_en()
+                                .clear_bit()
+                        );
+                    }
+                    else {
+                        conf0!($num).modify(|_, w| unsafe {
+                            // Configure memory block size
+                            w.mem_size()
+                                .bits(1)
+                        });
+                        conf1!($num).modify(|_, w|
+                            // The software owns the RAM until a reception is started
+                            w.mem_owner()
+                                .clear_bit()
+                        );
+                    }
+                };
+
+                #[cfg(esp32)]
+                conf0!($num).modify(|_, w|
+                    // Enable clock
+                    w.clk_en()
+                        .set_bit()
+                        // Disable forced power down of the peripheral (just to be sure)
+                        .mem_pd()
+                        .clear_bit()
+                );
+
+                #[cfg(not(esp32))]
+                channel.set_carrier_demodulation(false);
+                channel.set_filter_threshold(None);
+                channel.set_idle_threshold(0x7fff);
+                channel.set_channel_divider(1);
+
+                channel
+            }
+
+            /// Frequency of the clock ticks the channel counts pulse lengths in
+            pub fn tick_frequency(&self) -> HertzU32 {
+                cfg_if::cfg_if! {
+                    if #[cfg(any(esp32c3, esp32s3))] {
+                        let divider = rx_conf0!($num).read().div_cnt().bits();
+                        let source = self.sclk;
+                    } else {
+                        let divider = conf0!($num).read().div_cnt().bits();
+                        // The REF_TICK is always configured to 1 MHz
+                        let source = if conf1!($num).read().ref_always_on().bit_is_set() {
+                            self.sclk
+                        } else {
+                            HertzU32::MHz(1)
+                        };
+                    }
+                }
+
+                // A divider value of 0 divides by 256
+                match divider {
+                    0 => source / 256,
+                    divider => source / divider as u32,
+                }
+            }
+
+            /// Read a received sequence of pulse codes from the RMT RAM
+            ///
+            /// Returns the number of entries read and whether the end marker
+            /// of the sequence was found.
+            #[inline(always)]
+            fn read_sequence(&self, buffer: &mut [u32]) -> (usize, bool) {
+                let base_ptr: usize = RMT_RAM_START + ($num * CHANNEL_RAM_SIZE as usize * 4);
+
+                for index in 0..CHANNEL_RAM_SIZE as usize {
+                    let ram_ptr = (base_ptr + index * 4) as *const u32;
+                    let entry = unsafe { ram_ptr.read_volatile() };
+
+                    // An entry with a length of zero marks the end of the sequence
+                    if entry & 0x7fff == 0 {
+                        return (index, true);
+                    }
+
+                    match buffer.get_mut(index) {
+                        Some(slot) => *slot = entry,
+                        None => return (index, false),
+                    }
+
+                    if (entry >> 16) & 0x7fff == 0 {
+                        return (index + 1, true);
+                    }
+                }
+
+                (CHANNEL_RAM_SIZE as usize, true)
+            }
+
+            // Apply configuration updates (only required on the ESP32-C3 and ESP32-S3)
+            #[inline(always)]
+            fn update(&self) {
+                #[cfg(any(esp32c3, esp32s3))]
+                rx_conf1!($num).modify(|_, w| w.conf_update().set_bit());
+            }
+        }
+
+        paste!(
+            impl InputChannel<[<Configured $cxi>]> for $cxi {
+                /// Set the number of (divided) clock cycles without an edge after
+                /// which the reception of a sequence is considered finished
+                #[inline(always)]
+                fn set_idle_threshold(&mut self, threshold: u16) -> &mut Self {
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf0!($num)
+                                .modify(|_, w| unsafe { w.idle_thres().bits(threshold) });
+                        }
+                        else {
+                            conf0!($num)
+                                .modify(|_, w| unsafe { w.idle_thres().bits(threshold) });
+                        }
+                    };
+                    self.update();
+                    self
+                }
+
+                /// Set the glitch filter threshold, pulses shorter than `threshold`
+                /// source clock cycles are ignored (`None` disables the filter)
+                #[inline(always)]
+                fn set_filter_threshold(&mut self, threshold: Option<u8>) -> &mut Self {
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            let conf_reg = rx_conf1!($num);
+                        }
+                        else {
+                            let conf_reg = conf1!($num);
+                        }
+                    };
+                    conf_reg.modify(|_, w| unsafe {
+                        w.rx_filter_en()
+                            .bit(threshold.is_some())
+                            .rx_filter_thres()
+                            .bits(threshold.unwrap_or(0))
+                    });
+                    self.update();
+                    self
+                }
+
+                /// Set channel clock divider value
+                #[inline(always)]
+                fn set_channel_divider(&mut self, divider: u8) -> &mut Self {
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf0!($num)
+                                .modify(|_, w| unsafe { w.div_cnt().bits(divider) });
+                        }
+                        else {
+                            conf0!($num)
+                                .modify(|_, w| unsafe { w.div_cnt().bits(divider) });
+                        }
+                    };
+                    self.update();
+                    self
+                }
+
+                /// Enable/Disable carrier demodulation
+                #[cfg(not(esp32))]
+                #[inline(always)]
+                fn set_carrier_demodulation(&mut self, state: bool) -> &mut Self {
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf0!($num)
+                                .modify(|_, w| w.carrier_en().bit(state));
+                        }
+                        else {
+                            conf1!($num)
+                                .modify(|_, w| w.chk_rx_carrier_en().bit(state));
+                        }
+                    };
+                    self.update();
+                    self
+                }
+
+                /// Set the high and low thresholds (in divided clock cycles) of the
+                /// carrier that should be removed from the received signal
+                #[cfg(not(esp32))]
+                #[inline(always)]
+                fn set_carrier_thresholds(&mut self, high: u16, low: u16) -> &mut Self {
+                    rx_carrier_rm!($num).write(|w| unsafe {
+                        w.carrier_high_thres_ch()
+                            .bits(high)
+                            .carrier_low_thres_ch()
+                            .bits(low)
+                    });
+                    self
+                }
+
+                /// Set the level on which the carrier is present in the received
+                /// signal
+                #[cfg(not(esp32))]
+                #[inline(always)]
+                fn set_carrier_level(&mut self, level: bool) -> &mut Self {
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf0!($num)
+                                .modify(|_, w| w.carrier_out_lv().bit(level));
+                        }
+                        else {
+                            conf0!($num)
+                                .modify(|_, w| w.carrier_out_lv().bit(level));
+                        }
+                    };
+                    self.update();
+                    self
+                }
+
+                /// Set the clock source (for the ESP32-S2 and ESP32 this can be done on a
+                /// channel level)
+                #[cfg(any(esp32s2, esp32))]
+                #[inline(always)]
+                fn set_clock_source(&mut self, source: ClockSource) -> &mut Self {
+                    let bit_value = match source {
+                        ClockSource::RefTick => false,
+                        ClockSource::APB => true,
+                    };
+
+                    conf1!($num)
+                        .modify(|_, w| w.ref_always_on().bit(bit_value));
+                    self
+                }
+
+                /// Assign a pin that this channel should receive pulses from
+                fn assign_pin<RmtPin: InputPin>(
+                    self,
+                    mut pin: RmtPin,
+                ) -> [<Configured $cxi>] {
+                    // Configure Pin as input and connect to signal
+                    pin.set_to_input()
+                        .connect_input_to_peripheral($input_signal);
+
+                    [<Configured $cxi>] {
+                        channel: self,
+                    }
+                }
+            }
+
+            #[doc = "Wrapper for`" $cxi "` object."]
+            pub struct [<Configured $cxi>] {
+                channel: $cxi,
+            }
+
+            impl ConfiguredInputChannel for [<Configured $cxi>] {
+                /// Receive a pulse sequence in a blocking fashion
+                ///
+                /// Returns the number of pulse codes written into `buffer`. The last
+                /// entry may have a `length2` of zero, which marks the end of the
+                /// sequence.
+                fn receive_pulse_sequence(
+                    &mut self,
+                    buffer: &mut [PulseCode],
+                ) -> Result<usize, ReceiveError> {
+                    let mut raw_sequence = [0u32; CHANNEL_RAM_SIZE as usize];
+                    let len = buffer.len().min(raw_sequence.len());
+
+                    let received = self.receive_pulse_sequence_raw(&mut raw_sequence[..len])?;
+
+                    let tick_frequency = self.channel.tick_frequency();
+                    for (code, entry) in buffer.iter_mut().zip(raw_sequence[..received].iter()) {
+                        *code = PulseCode::from_raw(*entry, tick_frequency);
+                    }
+
+                    Ok(received)
+                }
+
+                /// Receive a raw pulse sequence in a blocking fashion
+                ///
+                /// The `buffer` elements are filled in the u32 format used by the RMT.
+                /// Please refer to the reference manual or use the variant which
+                /// returns `PulseCode` objects instead.
+                fn receive_pulse_sequence_raw(
+                    &mut self,
+                    buffer: &mut [u32],
+                ) -> Result<usize, ReceiveError> {
+                    // Reset the RAM write pointer and hand the RAM over to the receiver
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf1!($num).modify(|_, w| {
+                                w.mem_wr_rst()
+                                    .set_bit()
+                                    .apb_mem_rst()
+                                    .set_bit()
+                                    .mem_owner()
+                                    .set_bit()
+                                    .conf_update()
+                                    .set_bit()
+                            });
+                        } else {
+                            conf1!($num).modify(|_, w| {
+                                w.mem_wr_rst()
+                                    .set_bit()
+                                    .apb_mem_rst()
+                                    .set_bit()
+                                    .mem_owner()
+                                    .set_bit()
+                            });
+                        }
+                    }
+
+                    // Clear the relevant interrupts
+                    //
+                    // (since this is a write-through register, we can do this
+                    // safely for multiple separate channel instances without
+                    // having concurrency issues)
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32, esp32s2))] {
+                            unsafe { &*RMT::PTR }.int_clr.write(|w| {
+                                w.[<ch $num _rx_end_int_clr>]()
+                                    .set_bit()
+                                    .[<ch $num _err_int_clr>]()
+                                    .set_bit()
+                            });
+                        } else {
+                            unsafe { &*RMT::PTR }.int_clr.write(|w| {
+                                w.[<ch $num _rx_end_int_clr>]()
+                                    .set_bit()
+                                    .[<ch $num _rx_err_int_clr>]()
+                                    .set_bit()
+                            });
+                        }
+                    }
+
+                    // Start receiving, the reception begins with the first edge
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf1!($num)
+                                .modify(|_, w| w.rx_en().set_bit().conf_update().set_bit());
+                        } else {
+                            conf1!($num).modify(|_, w| w.rx_en().set_bit());
+                        }
+                    }
+
+                    // Wait for interrupt being raised, either completion or error
+                    let result = loop {
+                        let interrupts = unsafe { &*RMT::PTR }.int_raw.read();
+
+                        // The C3/S3 have a slightly different interrupt naming scheme
+                        #[cfg(any(esp32, esp32s2))]
+                        let error = interrupts.[<ch $num _err_int_raw>]().bit_is_set();
+                        #[cfg(any(esp32c3, esp32s3))]
+                        let error = interrupts.[<ch $num _rx_err_int_raw>]().bit_is_set();
+
+                        if error {
+                            break Err(ReceiveError::MemoryOverflow);
+                        }
+
+                        if interrupts.[<ch $num _rx_end_int_raw>]().bit_is_set() {
+                            break Ok(());
+                        }
+                    };
+
+                    self.stop_reception();
+                    result?;
+
+                    match self.channel.read_sequence(buffer) {
+                        (received, true) => Ok(received),
+                        (_, false) => Err(ReceiveError::BufferTooSmall),
+                    }
+                }
+
+                /// Stop any ongoing reception and hand the channel RAM back to the
+                /// software
+                fn stop_reception(&self) {
+                    cfg_if::cfg_if! {
+                        if #[cfg(any(esp32c3, esp32s3))] {
+                            rx_conf1!($num).modify(|_, w| {
+                                w.rx_en()
+                                    .clear_bit()
+                                    .mem_owner()
+                                    .clear_bit()
+                                    .conf_update()
+                                    .set_bit()
+                            });
+                        } else {
+                            conf1!($num).modify(|_, w| {
+                                w.rx_en()
+                                    .clear_bit()
+                                    .mem_owner()
+                                    .clear_bit()
+                            });
+                        }
+                    }
+                }
+
+                /// Frequency of the clock ticks the channel counts pulse lengths in
+                fn tick_frequency(&self) -> HertzU32 {
+                    self.channel.tick_frequency()
+                }
+            }
+        );
+    };
+}
+
+// On the ESP32 and ESP32-S2 every channel can either transmit or receive
+#[cfg(any(esp32, esp32s2))]
+macro_rules! convertible_channel {
+    ($num:literal, $cxi:ident, $rx_cxi:ident, $input_signal:path
+        ) => {
+        input_channel!($num, $rx_cxi, $input_signal);
+
+        impl $cxi {
+            /// Turn this channel into a receiver
+            pub fn into_rx(self) -> $rx_cxi {
+                $rx_cxi::new(self.sclk)
+            }
+        }
+
+        impl $rx_cxi {
+            /// Turn this channel into a transmitter
+            pub fn into_tx(self) -> $cxi {
+                $cxi::new(self.sclk)
+            }
+        }
+    };
+}
+
 #[cfg(esp32)]
 macro_rules! conf0 {
     ($channel: literal) => {
@@ -813,12 +2043,141 @@ macro_rules! conf1 {
     };
 }
 
+#[cfg(esp32)]
+macro_rules! carrier_duty {
+    ($channel: literal) => {
+        match $channel {
+            0 => &unsafe { &*RMT::PTR }.ch0carrier_duty,
+            1 => &unsafe { &*RMT::PTR }.ch1carrier_duty,
+            2 => &unsafe { &*RMT::PTR }.ch2carrier_duty,
+            3 => &unsafe { &*RMT::PTR }.ch3carrier_duty,
+            4 => &unsafe { &*RMT::PTR }.ch4carrier_duty,
+            5 => &unsafe { &*RMT::PTR }.ch5carrier_duty,
+            6 => &unsafe { &*RMT::PTR }.ch6carrier_duty,
+            7 => &unsafe { &*RMT::PTR }.ch7carrier_duty,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32c3)]
+macro_rules! carrier_duty {
+    ($channel: literal) => {
+        match $channel {
+            0 => &unsafe { &*RMT::PTR }.ch0carrier_duty,
+            1 => &unsafe { &*RMT::PTR }.ch1carrier_duty,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(any(esp32s2, esp32s3))]
+macro_rules! carrier_duty {
+    ($channel: literal) => {
+        match $channel {
+            0 => &unsafe { &*RMT::PTR }.ch0carrier_duty,
+            1 => &unsafe { &*RMT::PTR }.ch1carrier_duty,
+            2 => &unsafe { &*RMT::PTR }.ch2carrier_duty,
+            3 => &unsafe { &*RMT::PTR }.ch3carrier_duty,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32s2)]
+macro_rules! rx_carrier_rm {
+    ($channel: literal) => {
+        match $channel {
+            0 => &unsafe { &*RMT::PTR }.ch0_rx_carrier_rm,
+            1 => &unsafe { &*RMT::PTR }.ch1_rx_carrier_rm,
+            2 => &unsafe { &*RMT::PTR }.ch2_rx_carrier_rm,
+            3 => &unsafe { &*RMT::PTR }.ch3_rx_carrier_rm,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32c3)]
+macro_rules! rx_conf0 {
+    ($channel: literal) => {
+        match $channel {
+            2 => &unsafe { &*RMT::PTR }.ch2_rx_conf0,
+            3 => &unsafe { &*RMT::PTR }.ch3_rx_conf0,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32c3)]
+macro_rules! rx_conf1 {
+    ($channel: literal) => {
+        match $channel {
+            2 => &unsafe { &*RMT::PTR }.ch2_rx_conf1,
+            3 => &unsafe { &*RMT::PTR }.ch3_rx_conf1,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32c3)]
+macro_rules! rx_carrier_rm {
+    ($channel: literal) => {
+        match $channel {
+            2 => &unsafe { &*RMT::PTR }.ch2_rx_carrier_rm,
+            3 => &unsafe { &*RMT::PTR }.ch3_rx_carrier_rm,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32s3)]
+macro_rules! rx_conf0 {
+    ($channel: literal) => {
+        match $channel {
+            4 => &unsafe { &*RMT::PTR }.ch4_rx_conf0,
+            5 => &unsafe { &*RMT::PTR }.ch5_rx_conf0,
+            6 => &unsafe { &*RMT::PTR }.ch6_rx_conf0,
+            7 => &unsafe { &*RMT::PTR }.ch7_rx_conf0,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32s3)]
+macro_rules! rx_conf1 {
+    ($channel: literal) => {
+        match $channel {
+            4 => &unsafe { &*RMT::PTR }.ch4_rx_conf1,
+            5 => &unsafe { &*RMT::PTR }.ch5_rx_conf1,
+            6 => &unsafe { &*RMT::PTR }.ch6_rx_conf1,
+            7 => &unsafe { &*RMT::PTR }.ch7_rx_conf1,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
+#[cfg(esp32s3)]
+macro_rules! rx_carrier_rm {
+    ($channel: literal) => {
+        match $channel {
+            4 => &unsafe { &*RMT::PTR }.ch4_rx_carrier_rm,
+            5 => &unsafe { &*RMT::PTR }.ch5_rx_carrier_rm,
+            6 => &unsafe { &*RMT::PTR }.ch6_rx_carrier_rm,
+            7 => &unsafe { &*RMT::PTR }.ch7_rx_carrier_rm,
+            _ => panic!("Attempted access to non-existing channel!"),
+        }
+    };
+}
+
 macro_rules! rmt {
     (
         $global_conf_reg:ident,
         $(
             ($num:literal, $cxi:ident, $obj_name:ident, $output_signal:path),
         )+
+        $(
+            [$rx_num:literal, $rx_cxi:ident, $rx_obj_name:ident, $input_signal:path],
+        )*
     )
  => {
     /// RMT peripheral (RMT)
@@ -829,6 +2188,10 @@ macro_rules! rmt {
             /// RMT channel $cxi
             pub $obj_name: $cxi,
         )+
+        $(
+            /// RMT channel $rx_cxi
+            pub $rx_obj_name: $rx_cxi,
+        )*
     }
 
     impl PulseControl {
@@ -841,13 +2204,31 @@ macro_rules! rmt {
             div_abs: u8,
             div_frac_a: u8,
             div_frac_b: u8,
+            clocks: &Clocks,
         ) -> Result<Self, SetupError> {
+            let source_clock = match clk_source {
+                ClockSource::APB => clocks.apb_clock,
+                ClockSource::RTC20M => HertzU32::MHz(20),
+                ClockSource::XTAL => clocks.xtal_clock,
+            };
+
+            // divider = absolute_part + 1 + (fractional_part_a / fractional_part_b)
+            let sclk = if div_frac_b == 0 {
+                source_clock / (div_abs as u32 + 1)
+            } else {
+                let numerator = source_clock.raw() as u64 * div_frac_b as u64;
+                let denominator = (div_abs as u64 + 1) * div_frac_b as u64 + div_frac_a as u64;
+                HertzU32::from_raw((numerator / denominator) as u32)
+            };
 
             let pc = PulseControl {
                 reg: instance,
                 $(
-                    $obj_name: $cxi::new(),
+                    $obj_name: $cxi::new(sclk),
                 )+
+                $(
+                    $rx_obj_name: $rx_cxi::new(sclk),
+                )*
             };
 
             pc.enable_peripheral(peripheral_clock_control);
@@ -861,13 +2242,19 @@ macro_rules! rmt {
         pub fn new(
             instance: RMT,
             peripheral_clock_control: &mut PeripheralClockControl,
+            clocks: &Clocks,
         ) -> Result<Self, SetupError> {
+            // The channels select between the APB clock and the REF_TICK
+            let sclk = clocks.apb_clock;
 
             let pc = PulseControl {
                 reg: instance,
                 $(
-                    $obj_name: $cxi::new(),
+                    $obj_name: $cxi::new(sclk),
                 )+
+                $(
+                    $rx_obj_name: $rx_cxi::new(sclk),
+                )*
             };
 
             pc.enable_peripheral(peripheral_clock_control);
@@ -1006,6 +2393,9 @@ macro_rules! rmt {
         channel_instance!($num, $cxi, $output_signal);
         output_channel!($num, $cxi, $output_signal);
     )+
+    $(
+        input_channel!($rx_num, $rx_cxi, $input_signal);
+    )*
  };
 }
 
@@ -1014,6 +2404,8 @@ rmt!(
     sys_conf,
     (0, Channel0, channel0, OutputSignal::RMT_SIG_0),
     (1, Channel1, channel1, OutputSignal::RMT_SIG_1),
+    [2, Channel2, channel2, InputSignal::RMT_SIG_0],
+    [3, Channel3, channel3, InputSignal::RMT_SIG_1],
 );
 
 #[cfg(esp32s2)]
@@ -1025,6 +2417,15 @@ rmt!(
     (3, Channel3, channel3, OutputSignal::RMT_SIG_OUT3),
 );
 
+#[cfg(esp32s2)]
+convertible_channel!(0, Channel0, RxChannel0, InputSignal::RMT_SIG_IN0);
+#[cfg(esp32s2)]
+convertible_channel!(1, Channel1, RxChannel1, InputSignal::RMT_SIG_IN1);
+#[cfg(esp32s2)]
+convertible_channel!(2, Channel2, RxChannel2, InputSignal::RMT_SIG_IN2);
+#[cfg(esp32s2)]
+convertible_channel!(3, Channel3, RxChannel3, InputSignal::RMT_SIG_IN3);
+
 #[cfg(esp32)]
 rmt!(
     apb_conf,
@@ -1038,6 +2439,23 @@ rmt!(
     (7, Channel7, channel7, OutputSignal::RMT_SIG_7),
 );
 
+#[cfg(esp32)]
+convertible_channel!(0, Channel0, RxChannel0, InputSignal::RMT_SIG_0);
+#[cfg(esp32)]
+convertible_channel!(1, Channel1, RxChannel1, InputSignal::RMT_SIG_1);
+#[cfg(esp32)]
+convertible_channel!(2, Channel2, RxChannel2, InputSignal::RMT_SIG_2);
+#[cfg(esp32)]
+convertible_channel!(3, Channel3, RxChannel3, InputSignal::RMT_SIG_3);
+#[cfg(esp32)]
+convertible_channel!(4, Channel4, RxChannel4, InputSignal::RMT_SIG_4);
+#[cfg(esp32)]
+convertible_channel!(5, Channel5, RxChannel5, InputSignal::RMT_SIG_5);
+#[cfg(esp32)]
+convertible_channel!(6, Channel6, RxChannel6, InputSignal::RMT_SIG_6);
+#[cfg(esp32)]
+convertible_channel!(7, Channel7, RxChannel7, InputSignal::RMT_SIG_7);
+
 #[cfg(esp32s3)]
 rmt!(
     sys_conf,
@@ -1045,4 +2463,8 @@ rmt!(
     (1, Channel1, channel1, OutputSignal::RMT_SIG_OUT1),
     (2, Channel2, channel2, OutputSignal::RMT_SIG_OUT2),
     (3, Channel3, channel3, OutputSignal::RMT_SIG_OUT3),
+    [4, Channel4, channel4, InputSignal::RMT_SIG_IN0],
+    [5, Channel5, channel5, InputSignal::RMT_SIG_IN1],
+    [6, Channel6, channel6, InputSignal::RMT_SIG_IN2],
+    [7, Channel7, channel7, InputSignal::RMT_SIG_IN3],
 );
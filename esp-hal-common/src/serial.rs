@@ -11,6 +11,7 @@ use crate::{
         UART1,
     },
     types::{InputSignal, OutputSignal},
+    utils::ring_buffer::{Consumer, Producer, RingBuffer},
     InputPin,
     OutputPin,
 };
@@ -18,11 +19,28 @@ use crate::{
 const UART_FIFO_SIZE: u16 = 128;
 
 /// Custom serial error type
-#[derive(Debug)]
-pub enum Error {}
+///
+/// When a receive error is reported, the data in the RX FIFO is discarded, as
+/// it can't be told which of the bytes is affected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Error {
+    /// The RX FIFO overflowed, received data has been lost
+    FifoOverflow,
+    /// A received frame had no valid stop bit
+    Framing,
+    /// A received frame had a wrong parity bit
+    Parity,
+    /// A break condition was detected on the RX line
+    Break,
+    /// The received data didn't match the transmitted data in RS-485 mode
+    /// with collision detection
+    Rs485Collision,
+}
 
 /// UART configuration
 pub mod config {
+    use crate::clock::Clocks;
+
     /// Number of data bits
     #[derive(PartialEq, Eq, Copy, Clone, Debug)]
     pub enum DataBits {
@@ -51,6 +69,71 @@ pub mod config {
         STOP2   = 3,
     }
 
+    /// Clock source of the baud rate generator
+    #[derive(PartialEq, Eq, Copy, Clone, Debug)]
+    pub enum ClockSource {
+        /// APB clock, changes with the CPU frequency
+        Apb,
+        /// REF_TICK, 1 MHz
+        #[cfg(any(esp32, esp32s2))]
+        RefTick,
+        /// Crystal clock
+        #[cfg(any(esp32c3, esp32s3))]
+        Xtal,
+        /// RTC8M (RC_FAST) clock, about 17.5 MHz
+        ///
+        /// The internal RC oscillator isn't calibrated, its frequency may be
+        /// off by several percent depending on the chip and the temperature.
+        /// The baud rate is derived from the nominal frequency, so this is
+        /// only suitable for receivers tolerating such an error.
+        #[cfg(any(esp32c3, esp32s3))]
+        RcFast,
+    }
+
+    impl ClockSource {
+        pub(crate) fn frequency(&self, clocks: &Clocks) -> u32 {
+            match self {
+                ClockSource::Apb => clocks.apb_clock.to_Hz(),
+                #[cfg(any(esp32, esp32s2))]
+                ClockSource::RefTick => 1_000_000,
+                #[cfg(any(esp32c3, esp32s3))]
+                ClockSource::Xtal => clocks.xtal_clock.to_Hz(),
+                #[cfg(any(esp32c3, esp32s3))]
+                ClockSource::RcFast => 17_500_000,
+            }
+        }
+    }
+
+    /// Hardware flow control
+    #[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+    pub struct FlowControl {
+        /// Deassert RTS once the RX FIFO holds this many bytes (at most 127),
+        /// `None` disables RTS flow control
+        pub rts_threshold: Option<u16>,
+        /// Only transmit while CTS is asserted
+        pub cts: bool,
+    }
+
+    /// Inversion of the UART signals
+    #[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+    pub struct SignalInversion {
+        pub tx: bool,
+        pub rx: bool,
+        pub rts: bool,
+        pub cts: bool,
+    }
+
+    /// IrDA (SIR) encoding
+    #[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+    pub struct IrdaConfig {
+        /// Enable the IrDA duplex (loopback) mode
+        pub duplex: bool,
+        /// Invert the encoded TX signal
+        pub tx_inverted: bool,
+        /// Invert the encoded RX signal
+        pub rx_inverted: bool,
+    }
+
     /// UART configuration
     #[derive(Debug, Copy, Clone)]
     pub struct Config {
@@ -58,6 +141,17 @@ pub mod config {
         pub data_bits: DataBits,
         pub parity: Parity,
         pub stop_bits: StopBits,
+        /// Number of symbol times the RX line has to be idle before the RX
+        /// timeout is reported, `None` disables the timeout
+        pub rx_timeout: Option<u8>,
+        pub flow_control: FlowControl,
+        pub clock_source: ClockSource,
+        pub signal_inversion: SignalInversion,
+        /// IrDA encoding, `None` for plain UART signals
+        pub irda: Option<IrdaConfig>,
+        /// Number of RX edges which wake the chip from light sleep, `None`
+        /// disables the wakeup
+        pub wakeup_threshold: Option<u16>,
     }
 
     impl Config {
@@ -90,6 +184,36 @@ pub mod config {
             self.stop_bits = stop_bits;
             self
         }
+
+        pub fn rx_timeout(mut self, rx_timeout: Option<u8>) -> Self {
+            self.rx_timeout = rx_timeout;
+            self
+        }
+
+        pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+            self.flow_control = flow_control;
+            self
+        }
+
+        pub fn clock_source(mut self, clock_source: ClockSource) -> Self {
+            self.clock_source = clock_source;
+            self
+        }
+
+        pub fn signal_inversion(mut self, signal_inversion: SignalInversion) -> Self {
+            self.signal_inversion = signal_inversion;
+            self
+        }
+
+        pub fn irda(mut self, irda: Option<IrdaConfig>) -> Self {
+            self.irda = irda;
+            self
+        }
+
+        pub fn wakeup_threshold(mut self, wakeup_threshold: Option<u16>) -> Self {
+            self.wakeup_threshold = wakeup_threshold;
+            self
+        }
     }
 
     impl Default for Config {
@@ -99,10 +223,31 @@ pub mod config {
                 data_bits: DataBits::DataBits8,
                 parity: Parity::ParityNone,
                 stop_bits: StopBits::STOP1,
+                rx_timeout: None,
+                flow_control: FlowControl::default(),
+                clock_source: ClockSource::Apb,
+                signal_inversion: SignalInversion::default(),
+                irda: None,
+                wakeup_threshold: None,
             }
         }
     }
 
+    /// Configuration of the RS-485 half-duplex mode
+    ///
+    /// RTS drives the DE input of the transceiver, it is asserted while
+    /// transmitting.
+    #[derive(Debug, Copy, Clone, Default)]
+    pub struct Rs485Config {
+        /// Keep receiving while transmitting and report collisions, which
+        /// requires the receiver of the transceiver to stay enabled
+        pub collision_detection: bool,
+        /// Delay of the transmitted data in bit times (0 to 15)
+        pub tx_delay: u8,
+        /// Delay the received data by one bit time
+        pub rx_delay: bool,
+    }
+
     /// Configuration for the AT-CMD detection functionality
     pub struct AtCmdConfig {
         pub pre_idle_count: Option<u16>,
@@ -228,7 +373,13 @@ impl<TX: OutputPin, RX: InputPin> UartPins for TxRxPins<TX, RX> {
 #[cfg(feature = "eh1")]
 impl embedded_hal_1::serial::Error for Error {
     fn kind(&self) -> embedded_hal_1::serial::ErrorKind {
-        embedded_hal_1::serial::ErrorKind::Other
+        match self {
+            Error::FifoOverflow => embedded_hal_1::serial::ErrorKind::Overrun,
+            Error::Framing => embedded_hal_1::serial::ErrorKind::FrameFormat,
+            Error::Parity => embedded_hal_1::serial::ErrorKind::Parity,
+            Error::Break => embedded_hal_1::serial::ErrorKind::Other,
+            Error::Rs485Collision => embedded_hal_1::serial::ErrorKind::Other,
+        }
     }
 }
 
@@ -268,7 +419,12 @@ where
             serial.change_data_bits(config.data_bits);
             serial.change_parity(config.parity);
             serial.change_stop_bits(config.stop_bits);
-            serial.change_baud(config.baudrate, clocks);
+            serial.change_baud(config.baudrate, config.clock_source, clocks);
+            serial.set_rx_timeout(config.rx_timeout);
+            serial.set_flow_control(config.flow_control);
+            serial.set_signal_inversion(config.signal_inversion);
+            serial.set_irda(config.irda);
+            serial.set_light_sleep_wakeup(config.wakeup_threshold);
         });
 
         serial
@@ -289,9 +445,119 @@ where
     }
 
     /// Writes bytes
+    ///
+    /// In RS-485 mode this waits for the transmission to finish, so that the
+    /// transceiver can be switched back to receiving.
     pub fn write_bytes(&mut self, data: &[u8]) -> Result<(), Error> {
         data.iter()
-            .try_for_each(|c| nb::block!(self.write_byte(*c)))
+            .try_for_each(|c| nb::block!(self.write_byte(*c)))?;
+
+        if self.is_rs485_enabled() {
+            nb::block!(self.flush_tx())?;
+        }
+
+        Ok(())
+    }
+
+    /// Configures hardware flow control
+    pub fn set_flow_control(&mut self, flow_control: config::FlowControl) {
+        let reg_block = self.uart.register_block();
+
+        // A threshold beyond the size of the RX FIFO would never be reached
+        let rts_threshold = flow_control
+            .rts_threshold
+            .map(|threshold| u16::min(threshold, UART_FIFO_SIZE - 1));
+
+        #[cfg(esp32)]
+        if let Some(threshold) = rts_threshold {
+            reg_block
+                .conf1
+                .modify(|_, w| unsafe { w.rx_flow_thrhd().bits(threshold as u8) });
+        }
+
+        #[cfg(not(esp32))]
+        if let Some(threshold) = rts_threshold {
+            reg_block
+                .mem_conf
+                .modify(|_, w| unsafe { w.rx_flow_thrhd().bits(threshold) });
+        }
+
+        reg_block
+            .conf1
+            .modify(|_, w| w.rx_flow_en().bit(flow_control.rts_threshold.is_some()));
+        reg_block
+            .conf0
+            .modify(|_, w| w.tx_flow_en().bit(flow_control.cts));
+    }
+
+    /// Configures the RS-485 half-duplex mode, `None` disables it
+    ///
+    /// RTS is asserted while transmitting to enable the driver of the
+    /// transceiver, so hardware RTS flow control is disabled in RS-485 mode.
+    /// Writing data asserts RTS and enables the TX done interrupt, RTS is
+    /// released again by [`Serial::handle_rs485_tx_done`] (to be called from
+    /// the UART interrupt handler) or when the transmission is flushed.
+    pub fn set_rs485(&mut self, config: Option<config::Rs485Config>) {
+        let reg_block = self.uart.register_block();
+
+        if config.is_some() {
+            // RTS is released until data is written
+            reg_block.conf1.modify(|_, w| w.rx_flow_en().clear_bit());
+            reg_block.conf0.modify(|_, w| w.sw_rts().set_bit());
+        }
+
+        reg_block.rs485_conf.modify(|_, w| match config {
+            Some(config) => unsafe {
+                w.rs485_en()
+                    .set_bit()
+                    .rs485tx_rx_en()
+                    .bit(config.collision_detection)
+                    .rs485rxby_tx_en()
+                    .set_bit()
+                    .rs485_tx_dly_num()
+                    .bits(u8::min(config.tx_delay, 0xf))
+                    .rs485_rx_dly_num()
+                    .bit(config.rx_delay)
+            },
+            None => w
+                .rs485_en()
+                .clear_bit()
+                .rs485tx_rx_en()
+                .clear_bit()
+                .rs485rxby_tx_en()
+                .clear_bit(),
+        });
+    }
+
+    fn is_rs485_enabled(&self) -> bool {
+        self.uart
+            .register_block()
+            .rs485_conf
+            .read()
+            .rs485_en()
+            .bit_is_set()
+    }
+
+    /// Releases RTS once an RS-485 transmission is done
+    ///
+    /// Call this from the UART interrupt handler in RS-485 mode, so that the
+    /// transceiver switches back to receiving without flushing the
+    /// transmission. This also resets and stops listening for the TX done
+    /// interrupt.
+    pub fn handle_rs485_tx_done(&mut self) {
+        if self.is_rs485_enabled() && self.tx_done_interrupt_set() && self.uart.is_tx_idle() {
+            self.release_rts();
+            self.unlisten_tx_done();
+            self.reset_tx_done_interrupt();
+        }
+    }
+
+    // Releases RTS to switch the RS-485 transceiver back to receiving
+    fn release_rts(&self) {
+        self.uart
+            .register_block()
+            .conf0
+            .modify(|_, w| w.sw_rts().set_bit());
     }
 
     /// Configures the AT-CMD detection settings.
@@ -348,6 +614,180 @@ where
             .modify(|_, w| unsafe { w.rxfifo_full_thrhd().bits(threshold) });
     }
 
+    /// Configures the TX-FIFO threshold, below which the TX FIFO empty
+    /// interrupt is raised
+    pub fn set_tx_fifo_empty_threshold(&mut self, threshold: u16) {
+        #[cfg(esp32)]
+        let threshold: u8 = threshold as u8;
+
+        self.uart
+            .register_block()
+            .conf1
+            .modify(|_, w| unsafe { w.txfifo_empty_thrhd().bits(threshold) });
+    }
+
+    /// Configures the inversion of the UART signals
+    pub fn set_signal_inversion(&mut self, inversion: config::SignalInversion) {
+        self.uart.register_block().conf0.modify(|_, w| {
+            w.txd_inv()
+                .bit(inversion.tx)
+                .rxd_inv()
+                .bit(inversion.rx)
+                .rts_inv()
+                .bit(inversion.rts)
+                .cts_inv()
+                .bit(inversion.cts)
+        });
+    }
+
+    /// Configures the IrDA encoding, `None` disables it
+    pub fn set_irda(&mut self, config: Option<config::IrdaConfig>) {
+        let config = config.map(|config| (true, config)).unwrap_or_default();
+
+        self.uart.register_block().conf0.modify(|_, w| {
+            w.irda_en()
+                .bit(config.0)
+                .irda_tx_en()
+                .bit(config.0)
+                .irda_dplx()
+                .bit(config.1.duplex)
+                .irda_tx_inv()
+                .bit(config.1.tx_inverted)
+                .irda_rx_inv()
+                .bit(config.1.rx_inverted)
+        });
+    }
+
+    /// Configures waking the chip from light sleep by UART activity
+    ///
+    /// The chip wakes up once `edges` positive edges have been seen on the RX
+    /// line, `None` disables the wakeup. The data causing the wakeup is not
+    /// received. Only UART0 and UART1 can wake the chip.
+    pub fn set_light_sleep_wakeup(&mut self, edges: Option<u16>) {
+        #[cfg(any(esp32, esp32s2))]
+        const MIN_WAKEUP_THRESHOLD: u16 = 2;
+        #[cfg(any(esp32c3, esp32s3))]
+        const MIN_WAKEUP_THRESHOLD: u16 = 3;
+
+        if let Some(edges) = edges {
+            let threshold = edges.saturating_sub(MIN_WAKEUP_THRESHOLD);
+            self.uart
+                .register_block()
+                .sleep_conf
+                .write(|w| unsafe { w.active_threshold().bits(u16::min(threshold, 0x3ff)) });
+        }
+
+        if let Some(wakeup_source) = self.uart.wakeup_source() {
+            let rtc_cntl = unsafe { &*crate::pac::RTC_CNTL::PTR };
+            rtc_cntl.wakeup_state.modify(|r, w| unsafe {
+                let sources = r.wakeup_ena().bits() as u32;
+                let sources = match edges {
+                    Some(_) => sources | wakeup_source,
+                    None => sources & !wakeup_source,
+                };
+                w.wakeup_ena().bits(sources as _)
+            });
+        }
+    }
+
+    /// Configures the RX timeout
+    ///
+    /// The timeout is reported once the RX line has been idle for `timeout`
+    /// symbol times after receiving data, `None` disables it. The timeout is
+    /// limited to 126 symbols on the ESP32 and to 1023 bit times on the other
+    /// chips.
+    pub fn set_rx_timeout(&mut self, timeout: Option<u8>) {
+        let reg_block = self.uart.register_block();
+
+        #[cfg(esp32)]
+        if let Some(timeout) = timeout {
+            reg_block
+                .conf1
+                .modify(|_, w| unsafe { w.rx_tout_thrhd().bits(u8::min(timeout, 0x7e)) });
+        }
+
+        #[cfg(not(esp32))]
+        if let Some(timeout) = timeout {
+            // the threshold is given in bit times
+            let threshold = u16::min(timeout as u16 * self.symbol_length() as u16, 0x3ff);
+            reg_block
+                .mem_conf
+                .modify(|_, w| unsafe { w.rx_tout_thrhd().bits(threshold) });
+        }
+
+        reg_block
+            .conf1
+            .modify(|_, w| w.rx_tout_en().bit(timeout.is_some()));
+    }
+
+    /// Number of bits in a frame with the current configuration
+    #[cfg(not(esp32))]
+    fn symbol_length(&self) -> u8 {
+        let conf0 = self.uart.register_block().conf0.read();
+
+        let data_bits = conf0.bit_num().bits() + 5;
+        let parity_bits = conf0.parity_en().bit_is_set() as u8;
+        let stop_bits = match conf0.stop_bit_num().bits() {
+            1 => 1,
+            _ => 2,
+        };
+
+        1 + data_bits + parity_bits + stop_bits
+    }
+
+    /// Reads bytes until the RX line becomes idle
+    ///
+    /// Blocks until data has been received and the RX timeout configured with
+    /// [`Serial::set_rx_timeout`] has passed, or until `buf` is full. Returns
+    /// the number of bytes read. Without an RX timeout this only returns once
+    /// `buf` is full.
+    pub fn read_until_idle(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let timeout_enabled = self
+            .uart
+            .register_block()
+            .conf1
+            .read()
+            .rx_tout_en()
+            .bit_is_set();
+        let mut count = 0;
+
+        while count < buf.len() {
+            let idle = self.rx_timeout_interrupt_set();
+            let available = self.uart.get_rx_fifo_count() as usize;
+            let remaining = buf.len() - count;
+
+            // Leave a byte in the FIFO while the line is active, the timeout is
+            // only detected while the FIFO holds data. This isn't needed once
+            // the remaining part of `buf` can be filled.
+            let to_read = if idle || !timeout_enabled || available >= remaining {
+                usize::min(available, remaining)
+            } else {
+                available.saturating_sub(1)
+            };
+
+            for _ in 0..to_read {
+                match self.read_byte() {
+                    Ok(byte) => {
+                        buf[count] = byte;
+                        count += 1;
+                    }
+                    Err(nb::Error::WouldBlock) => break,
+                    Err(nb::Error::Other(err)) => return Err(err),
+                }
+            }
+
+            if idle {
+                self.reset_rx_timeout_interrupt();
+
+                if count > 0 {
+                    break;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+
     /// Listen for AT-CMD interrupts
     pub fn listen_at_cmd(&mut self) {
         self.uart
@@ -361,7 +801,7 @@ where
         self.uart
             .register_block()
             .int_ena
-            .modify(|_, w| w.at_cmd_char_det_int_ena().set_bit());
+            .modify(|_, w| w.at_cmd_char_det_int_ena().clear_bit());
     }
 
     /// Listen for TX-DONE interrupts
@@ -377,7 +817,7 @@ where
         self.uart
             .register_block()
             .int_ena
-            .modify(|_, w| w.tx_done_int_ena().set_bit());
+            .modify(|_, w| w.tx_done_int_ena().clear_bit());
     }
 
     /// Listen for RX-FIFO-FULL interrupts
@@ -393,7 +833,55 @@ where
         self.uart
             .register_block()
             .int_ena
-            .modify(|_, w| w.rxfifo_full_int_ena().set_bit());
+            .modify(|_, w| w.rxfifo_full_int_ena().clear_bit());
+    }
+
+    /// Listen for RX timeout interrupts
+    pub fn listen_rx_timeout(&mut self) {
+        self.uart
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.rxfifo_tout_int_ena().set_bit());
+    }
+
+    /// Stop listening for RX timeout interrupts
+    pub fn unlisten_rx_timeout(&mut self) {
+        self.uart
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.rxfifo_tout_int_ena().clear_bit());
+    }
+
+    /// Listen for RS-485 collision interrupts
+    pub fn listen_rs485_collision(&mut self) {
+        self.uart
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.rs485_clash_int_ena().set_bit());
+    }
+
+    /// Stop listening for RS-485 collision interrupts
+    pub fn unlisten_rs485_collision(&mut self) {
+        self.uart
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.rs485_clash_int_ena().clear_bit());
+    }
+
+    /// Listen for TX-FIFO empty interrupts
+    pub fn listen_tx_fifo_empty(&mut self) {
+        self.uart
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.txfifo_empty_int_ena().set_bit());
+    }
+
+    /// Stop listening for TX-FIFO empty interrupts
+    pub fn unlisten_tx_fifo_empty(&mut self) {
+        self.uart
+            .register_block()
+            .int_ena
+            .modify(|_, w| w.txfifo_empty_int_ena().clear_bit());
     }
 
     /// Checks if AT-CMD interrupt is set
@@ -426,6 +914,36 @@ where
             .bit_is_set()
     }
 
+    /// Checks if RX timeout interrupt is set
+    pub fn rx_timeout_interrupt_set(&self) -> bool {
+        self.uart
+            .register_block()
+            .int_raw
+            .read()
+            .rxfifo_tout_int_raw()
+            .bit_is_set()
+    }
+
+    /// Checks if RS-485 collision interrupt is set
+    pub fn rs485_collision_interrupt_set(&self) -> bool {
+        self.uart
+            .register_block()
+            .int_raw
+            .read()
+            .rs485_clash_int_raw()
+            .bit_is_set()
+    }
+
+    /// Checks if TX-FIFO empty interrupt is set
+    pub fn tx_fifo_empty_interrupt_set(&self) -> bool {
+        self.uart
+            .register_block()
+            .int_raw
+            .read()
+            .txfifo_empty_int_raw()
+            .bit_is_set()
+    }
+
     /// Reset AT-CMD interrupt
     pub fn reset_at_cmd_interrupt(&self) {
         self.uart
@@ -450,13 +968,54 @@ where
             .write(|w| w.rxfifo_full_int_clr().set_bit());
     }
 
+    /// Reset RX timeout interrupt
+    pub fn reset_rx_timeout_interrupt(&self) {
+        self.uart
+            .register_block()
+            .int_clr
+            .write(|w| w.rxfifo_tout_int_clr().set_bit());
+    }
+
+    /// Reset RS-485 collision interrupt
+    pub fn reset_rs485_collision_interrupt(&self) {
+        self.uart
+            .register_block()
+            .int_clr
+            .write(|w| w.rs485_clash_int_clr().set_bit());
+    }
+
+    /// Reset TX-FIFO empty interrupt
+    pub fn reset_tx_fifo_empty_interrupt(&self) {
+        self.uart
+            .register_block()
+            .int_clr
+            .write(|w| w.txfifo_empty_int_clr().set_bit());
+    }
+
     fn write_byte(&mut self, word: u8) -> nb::Result<(), Error> {
         if self.uart.get_tx_fifo_count() < UART_FIFO_SIZE {
+            let rs485 = self.is_rs485_enabled();
+
+            // assert RTS to enable the driver of the RS-485 transceiver
+            if rs485 {
+                self.uart
+                    .register_block()
+                    .conf0
+                    .modify(|_, w| w.sw_rts().clear_bit());
+            }
+
             self.uart
                 .register_block()
                 .fifo
                 .write(|w| unsafe { w.rxfifo_rd_byte().bits(word) });
 
+            // the TX done interrupt releases RTS again, a stale one from a
+            // previous transmission must not do so
+            if rs485 {
+                self.reset_tx_done_interrupt();
+                self.listen_tx_done();
+            }
+
             Ok(())
         } else {
             Err(nb::Error::WouldBlock)
@@ -465,13 +1024,72 @@ where
 
     fn flush_tx(&self) -> nb::Result<(), Error> {
         if self.uart.is_tx_idle() {
+            if self.is_rs485_enabled() {
+                self.release_rts();
+
+                if self.rs485_collision_interrupt_set() {
+                    self.reset_rs485_collision_interrupt();
+                    return Err(nb::Error::Other(Error::Rs485Collision));
+                }
+            }
+
             Ok(())
         } else {
             Err(nb::Error::WouldBlock)
         }
     }
 
+    /// Checks for receive errors and resets the reported one
+    ///
+    /// The hardware doesn't tell which byte in the RX FIFO is affected by an
+    /// error, so the RX FIFO is drained when one is reported. The corrupted
+    /// byte is never returned as valid data, but the bytes received right
+    /// before it are discarded as well.
+    fn check_rx_errors(&mut self) -> Result<(), Error> {
+        let reg_block = self.uart.register_block();
+        let int_raw = reg_block.int_raw.read();
+
+        let result = if int_raw.rxfifo_ovf_int_raw().bit_is_set() {
+            reg_block
+                .int_clr
+                .write(|w| w.rxfifo_ovf_int_clr().set_bit());
+            Err(Error::FifoOverflow)
+        } else if int_raw.brk_det_int_raw().bit_is_set() {
+            reg_block.int_clr.write(|w| w.brk_det_int_clr().set_bit());
+            Err(Error::Break)
+        } else if int_raw.frm_err_int_raw().bit_is_set() {
+            reg_block.int_clr.write(|w| w.frm_err_int_clr().set_bit());
+            Err(Error::Framing)
+        } else if int_raw.parity_err_int_raw().bit_is_set() {
+            reg_block
+                .int_clr
+                .write(|w| w.parity_err_int_clr().set_bit());
+            Err(Error::Parity)
+        } else {
+            Ok(())
+        };
+
+        if result.is_err() {
+            while self.uart.get_rx_fifo_count() > 0 {
+                self.read_rx_fifo();
+            }
+        }
+
+        result
+    }
+
     fn read_byte(&mut self) -> nb::Result<u8, Error> {
+        self.check_rx_errors()?;
+
+        if self.uart.get_rx_fifo_count() > 0 {
+            Ok(self.read_rx_fifo())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    // Reads a byte from the RX FIFO, which must not be empty
+    fn read_rx_fifo(&mut self) -> u8 {
         #[allow(unused_variables)]
         let offset = 0;
 
@@ -479,16 +1097,10 @@ where
         #[cfg(esp32s2)]
         let offset = 0x20c00000;
 
-        if self.uart.get_rx_fifo_count() > 0 {
-            let value = unsafe {
-                let fifo = (self.uart.register_block().fifo.as_ptr() as *mut u8).offset(offset)
-                    as *mut crate::pac::generic::Reg<FIFO_SPEC>;
-                (*fifo).read().rxfifo_rd_byte().bits()
-            };
-
-            Ok(value)
-        } else {
-            Err(nb::Error::WouldBlock)
+        unsafe {
+            let fifo = (self.uart.register_block().fifo.as_ptr() as *mut u8).offset(offset)
+                as *mut crate::pac::generic::Reg<FIFO_SPEC>;
+            (*fifo).read().rxfifo_rd_byte().bits()
         }
     }
 
@@ -551,58 +1163,176 @@ where
         self
     }
 
-    #[cfg(any(esp32c3, esp32s3))]
-    fn change_baud(&self, baudrate: u32, clocks: &Clocks) {
-        // we force the clock source to be APB and don't use the decimal part of the
-        // divider
-        let clk = clocks.apb_clock.to_Hz();
-        let max_div = 0b1111_1111_1111 - 1;
-        let clk_div = ((clk) + (max_div * baudrate) - 1) / (max_div * baudrate);
-
-        self.uart.register_block().clk_conf.write(|w| unsafe {
-            w.sclk_sel()
-                .bits(1) // APB
-                .sclk_div_a()
-                .bits(0)
-                .sclk_div_b()
-                .bits(0)
-                .sclk_div_num()
-                .bits(clk_div as u8 - 1)
-                .rx_sclk_en()
-                .bit(true)
-                .tx_sclk_en()
-                .bit(true)
+    /// Change the baud rate and the clock source used to generate it
+    ///
+    /// The APB clock follows changes of the CPU frequency, the other clock
+    /// sources keep the baud rate stable. Selecting
+    /// [`config::ClockSource::RcFast`] powers up the RC oscillator and enables
+    /// its clock for the digital peripherals.
+    pub fn change_baud(
+        &mut self,
+        baudrate: u32,
+        clock_source: config::ClockSource,
+        clocks: &Clocks,
+    ) -> &mut Self {
+        let reg_block = self.uart.register_block();
+        let clk = clock_source.frequency(clocks);
+
+        #[cfg(any(esp32c3, esp32s3))]
+        if clock_source == config::ClockSource::RcFast {
+            enable_rc_fast_clock();
+        }
+
+        // The integer part of the divider has 12 bits, on the ESP32-C3 and
+        // ESP32-S3 the clock can be divided (by up to 256) beforehand to reach
+        // lower rates
+        #[cfg(any(esp32c3, esp32s3))]
+        let clk = {
+            let max_div: u64 = 0b1111_1111_1111 - 1;
+            let sclk_div =
+                (clk as u64 + max_div * baudrate as u64 - 1) / (max_div * baudrate as u64);
+            let sclk_div = sclk_div.clamp(1, 256) as u32;
+
+            reg_block.clk_conf.modify(|_, w| unsafe {
+                w.sclk_sel()
+                    .bits(match clock_source {
+                        config::ClockSource::Apb => 1,
+                        config::ClockSource::RcFast => 2,
+                        config::ClockSource::Xtal => 3,
+                    })
+                    .sclk_div_a()
+                    .bits(0)
+                    .sclk_div_b()
+                    .bits(0)
+                    .sclk_div_num()
+                    .bits((sclk_div - 1) as u8)
+                    .rx_sclk_en()
+                    .bit(true)
+                    .tx_sclk_en()
+                    .bit(true)
+            });
+
+            clk / sclk_div
+        };
+
+        #[cfg(any(esp32, esp32s2))]
+        reg_block.conf0.modify(|_, w| {
+            w.tick_ref_always_on()
+                .bit(clock_source == config::ClockSource::Apb)
         });
 
-        let clk = clk / clk_div;
-        let divider = clk / baudrate;
-        let divider = divider as u16;
+        // The divider has 4 fractional bits
+        let divider = ((clk as u64) * 16 + baudrate as u64 / 2) / baudrate as u64;
 
-        self.uart
-            .register_block()
-            .clkdiv
-            .write(|w| unsafe { w.clkdiv().bits(divider).frag().bits(0) });
+        reg_block.clkdiv.write(|w| unsafe {
+            w.clkdiv()
+                .bits((divider >> 4) as _)
+                .frag()
+                .bits((divider & 0xf) as u8)
+        });
+
+        self
+    }
+
+    /// Start detecting the baud rate of the received data
+    ///
+    /// The detection needs a few edges on the RX line, the detected rate can be
+    /// read with [`Serial::detected_baudrate`].
+    pub fn start_autobaud(&mut self) {
+        self.set_autobaud(false);
+        self.set_autobaud(true);
     }
 
-    #[cfg(any(esp32, esp32s2))]
-    fn change_baud(&self, baudrate: u32, clocks: &Clocks) {
-        // we force the clock source to be APB and don't use the decimal part of the
-        // divider
+    /// Stop detecting the baud rate
+    pub fn stop_autobaud(&mut self) {
+        self.set_autobaud(false);
+    }
+
+    /// The baud rate detected since [`Serial::start_autobaud`]
+    ///
+    /// Returns `None` until at least `min_edges` edges have been seen on the RX
+    /// line. The rate is derived from the shortest low and high pulses, so the
+    /// received data has to contain single bits of both levels, like `0x55`.
+    pub fn detected_baudrate(&self, min_edges: u16, clocks: &Clocks) -> Option<u32> {
+        let reg_block = self.uart.register_block();
+
+        #[cfg(esp32)]
+        let (edges, low, high) = (
+            reg_block.rxd_cnt.read().edge_cnt().bits(),
+            reg_block.lowpulse.read().min_cnt().bits() as u32,
+            reg_block.highpulse.read().min_cnt().bits() as u32,
+        );
+
+        #[cfg(not(esp32))]
+        let (edges, low, high) = (
+            reg_block.rxd_cnt.read().rxd_edge_cnt().bits(),
+            reg_block.lowpulse.read().lowpulse_min_cnt().bits() as u32,
+            reg_block.highpulse.read().highpulse_min_cnt().bits() as u32,
+        );
+
+        if edges < min_edges {
+            return None;
+        }
+
+        // The pulses are measured in cycles of the UART clock
+        #[cfg(any(esp32, esp32s2))]
         let clk = clocks.apb_clock.to_Hz();
 
+        #[cfg(any(esp32c3, esp32s3))]
+        let clk = match reg_block.clk_conf.read().sclk_sel().bits() {
+            2 => config::ClockSource::RcFast,
+            3 => config::ClockSource::Xtal,
+            _ => config::ClockSource::Apb,
+        }
+        .frequency(clocks);
+
+        // A bit takes the average of the shortest low and high pulse, each
+        // count is one less than the pulse length
+        Some((clk as u64 * 2 / (low as u64 + high as u64 + 2)) as u32)
+    }
+
+    #[cfg(esp32)]
+    fn set_autobaud(&mut self, enable: bool) {
         self.uart
             .register_block()
-            .conf0
-            .modify(|_, w| w.tick_ref_always_on().bit(true));
-        let divider = clk / baudrate;
+            .auto_baud
+            .modify(|_, w| w.en().bit(enable));
+    }
 
+    #[cfg(not(esp32))]
+    fn set_autobaud(&mut self, enable: bool) {
         self.uart
             .register_block()
-            .clkdiv
-            .write(|w| unsafe { w.clkdiv().bits(divider).frag().bits(0) });
+            .conf0
+            .modify(|_, w| w.autobaud_en().bit(enable));
     }
 }
 
+/// Powers up the RC_FAST oscillator and enables its clock gate to the digital
+/// peripherals
+#[cfg(any(esp32c3, esp32s3))]
+fn enable_rc_fast_clock() {
+    let rtc_cntl = unsafe { &*crate::pac::RTC_CNTL::PTR };
+
+    if rtc_cntl.clk_conf.read().enb_ck8m().bit_is_set() {
+        rtc_cntl.clk_conf.modify(|_, w| w.enb_ck8m().clear_bit());
+
+        // give the oscillator time to start
+        unsafe { crate::rom::esp_rom_delay_us(50) };
+    }
+
+    rtc_cntl.clk_conf.modify(|_, w| w.dig_clk8m_en().set_bit());
+}
+
+fn is_tx_idle(register_block: &RegisterBlock) -> bool {
+    #[cfg(esp32)]
+    let idle = register_block.status.read().st_utx_out().bits() == 0x0u8;
+    #[cfg(not(esp32))]
+    let idle = register_block.fsm_status.read().st_utx_out().bits() == 0x0u8;
+
+    idle
+}
+
 /// UART peripheral instance
 pub trait Instance {
     fn register_block(&self) -> &RegisterBlock;
@@ -639,6 +1369,12 @@ pub trait Instance {
                 .set_bit()
                 .rxfifo_tout_int_clr()
                 .set_bit()
+                .frm_err_int_clr()
+                .set_bit()
+                .parity_err_int_clr()
+                .set_bit()
+                .brk_det_int_clr()
+                .set_bit()
         });
 
         self.register_block().int_ena.write(|w| {
@@ -648,6 +1384,12 @@ pub trait Instance {
                 .clear_bit()
                 .rxfifo_tout_int_ena()
                 .clear_bit()
+                .frm_err_int_ena()
+                .clear_bit()
+                .parity_err_int_ena()
+                .clear_bit()
+                .brk_det_int_ena()
+                .clear_bit()
         });
     }
 
@@ -670,12 +1412,7 @@ pub trait Instance {
     }
 
     fn is_tx_idle(&self) -> bool {
-        #[cfg(esp32)]
-        let idle = self.register_block().status.read().st_utx_out().bits() == 0x0u8;
-        #[cfg(not(esp32))]
-        let idle = self.register_block().fsm_status.read().st_utx_out().bits() == 0x0u8;
-
-        idle
+        is_tx_idle(self.register_block())
     }
 
     fn is_rx_idle(&self) -> bool {
@@ -694,6 +1431,9 @@ pub trait Instance {
     fn cts_signal(&self) -> InputSignal;
 
     fn rts_signal(&self) -> OutputSignal;
+
+    /// The light sleep wakeup source of the UART, if it has one
+    fn wakeup_source(&self) -> Option<u32>;
 }
 
 impl Instance for UART0 {
@@ -717,6 +1457,10 @@ impl Instance for UART0 {
     fn rts_signal(&self) -> OutputSignal {
         OutputSignal::U0RTS
     }
+
+    fn wakeup_source(&self) -> Option<u32> {
+        Some(1 << 2)
+    }
 }
 
 impl Instance for UART1 {
@@ -740,6 +1484,10 @@ impl Instance for UART1 {
     fn rts_signal(&self) -> OutputSignal {
         OutputSignal::U1RTS
     }
+
+    fn wakeup_source(&self) -> Option<u32> {
+        Some(1 << 3)
+    }
 }
 
 #[cfg(any(esp32, esp32s3))]
@@ -764,6 +1512,10 @@ impl Instance for UART2 {
     fn rts_signal(&self) -> OutputSignal {
         OutputSignal::U2RTS
     }
+
+    fn wakeup_source(&self) -> Option<u32> {
+        None
+    }
 }
 
 #[cfg(feature = "ufmt")]
@@ -849,3 +1601,356 @@ where
         self.flush_tx()
     }
 }
+
+/// Statistics of a [`BufferedSerial`]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct BufferedSerialStats {
+    /// Bytes dropped because the RX buffer was full
+    pub rx_buffer_overflows: usize,
+    /// Overflows of the RX FIFO, because it wasn't emptied in time
+    pub rx_fifo_overflows: usize,
+    /// Received bytes with a framing or parity error and detected breaks
+    pub rx_errors: usize,
+}
+
+/// Interrupt driven UART driver with RX and TX ring buffers
+///
+/// The UART interrupt has to call [`BufferedSerial::handle_interrupt`], which
+/// moves received data from the RX FIFO into the RX buffer and refills the TX
+/// FIFO from the TX buffer. As the interrupt handler and the application share
+/// the instance, it's usually kept in a `critical_section::Mutex`. Use
+/// [`BufferedSerial::split`] to only share the [`BufferedSerialHandler`] with
+/// the interrupt and access the buffers through independent TX and RX halves.
+///
+/// RX data is moved when the RX FIFO reaches the threshold set by
+/// [`Serial::set_rx_fifo_full_threshold`] and when the RX timeout configured by
+/// [`Serial::set_rx_timeout`] passes, so an RX timeout should be set. The TX
+/// FIFO is refilled when it drops below the threshold set by
+/// [`Serial::set_tx_fifo_empty_threshold`].
+pub struct BufferedSerial<'d, T> {
+    handler: BufferedSerialHandler<'d, T>,
+    tx: BufferedTx<'d>,
+    rx: BufferedRx<'d>,
+}
+
+impl<'d, T> BufferedSerial<'d, T>
+where
+    T: Instance,
+{
+    /// Create a buffered UART driver and enable its RX interrupts
+    ///
+    /// Data left in the buffers is dropped.
+    pub fn new(
+        serial: Serial<T>,
+        rx_buffer: &'d mut RingBuffer,
+        tx_buffer: &'d mut RingBuffer,
+    ) -> Self {
+        // The registers are memory mapped and valid for the whole program, the
+        // TX half only uses them to enable the TX FIFO empty interrupt and to
+        // check if the transmission is done
+        let registers = unsafe { &*(serial.uart.register_block() as *const RegisterBlock) };
+        let (rx_producer, rx_consumer) = rx_buffer.split();
+        let (tx_producer, tx_consumer) = tx_buffer.split();
+
+        let mut handler = BufferedSerialHandler {
+            serial,
+            rx: rx_producer,
+            tx: tx_consumer,
+            stats: BufferedSerialStats::default(),
+        };
+        handler.serial.listen_rx_fifo_full();
+        handler.serial.listen_rx_timeout();
+
+        BufferedSerial {
+            handler,
+            tx: BufferedTx {
+                registers,
+                buffer: tx_producer,
+            },
+            rx: BufferedRx {
+                buffer: rx_consumer,
+            },
+        }
+    }
+
+    /// Move data between the FIFOs and the buffers and reset the interrupts
+    pub fn handle_interrupt(&mut self) {
+        self.handler.handle_interrupt();
+    }
+
+    /// Split into the interrupt handler and independent TX and RX halves
+    ///
+    /// The halves access the buffers without locking, only the handler has to
+    /// be shared with the UART interrupt.
+    pub fn split(self) -> (BufferedSerialHandler<'d, T>, BufferedTx<'d>, BufferedRx<'d>) {
+        (self.handler, self.tx, self.rx)
+    }
+
+    /// Statistics of lost and corrupted data
+    pub fn stats(&self) -> BufferedSerialStats {
+        self.handler.stats()
+    }
+
+    /// Reset the statistics
+    pub fn reset_stats(&mut self) {
+        self.handler.reset_stats();
+    }
+
+    /// Disable the interrupts and return the UART driver
+    ///
+    /// Data remaining in the buffers is dropped.
+    pub fn free(mut self) -> Serial<T> {
+        self.handler.serial.unlisten_rx_fifo_full();
+        self.handler.serial.unlisten_rx_timeout();
+        self.handler.serial.unlisten_tx_fifo_empty();
+
+        self.handler.serial
+    }
+
+    fn read_byte(&mut self) -> nb::Result<u8, Error> {
+        // pick up data which didn't trigger an interrupt yet
+        self.handler.receive();
+        self.rx.read_byte()
+    }
+
+    fn write_byte(&mut self, word: u8) -> nb::Result<(), Error> {
+        self.tx.write_byte(word)?;
+
+        // start the transmission, the interrupt keeps it going
+        self.handler.transmit();
+
+        Ok(())
+    }
+
+    fn flush_tx(&mut self) -> nb::Result<(), Error> {
+        self.handler.transmit();
+        self.tx.flush_tx()?;
+
+        // releases RTS in RS-485 mode and reports collisions
+        self.handler.serial.flush_tx()
+    }
+}
+
+/// Interrupt handler of a split [`BufferedSerial`]
+pub struct BufferedSerialHandler<'d, T> {
+    serial: Serial<T>,
+    rx: Producer<'d>,
+    tx: Consumer<'d>,
+    stats: BufferedSerialStats,
+}
+
+impl<'d, T> BufferedSerialHandler<'d, T>
+where
+    T: Instance,
+{
+    /// Move data between the FIFOs and the buffers and reset the interrupts
+    ///
+    /// In RS-485 mode this also releases RTS once the transmission is done.
+    pub fn handle_interrupt(&mut self) {
+        self.receive();
+        self.transmit();
+        self.serial.handle_rs485_tx_done();
+
+        self.serial.reset_rx_fifo_full_interrupt();
+        self.serial.reset_rx_timeout_interrupt();
+    }
+
+    /// Statistics of lost and corrupted data
+    pub fn stats(&self) -> BufferedSerialStats {
+        self.stats
+    }
+
+    /// Reset the statistics
+    pub fn reset_stats(&mut self) {
+        self.stats = BufferedSerialStats::default();
+    }
+
+    fn receive(&mut self) {
+        loop {
+            match self.serial.read_byte() {
+                Ok(byte) => {
+                    if !self.rx.push(byte) {
+                        self.stats.rx_buffer_overflows += 1;
+                    }
+                }
+                Err(nb::Error::Other(Error::FifoOverflow)) => self.stats.rx_fifo_overflows += 1,
+                Err(nb::Error::Other(_)) => self.stats.rx_errors += 1,
+                Err(nb::Error::WouldBlock) => break,
+            }
+        }
+    }
+
+    fn transmit(&mut self) {
+        while self.serial.uart.get_tx_fifo_count() < UART_FIFO_SIZE {
+            match self.tx.pop() {
+                Some(byte) => self.serial.write_byte(byte).ok(),
+                None => break,
+            };
+        }
+
+        self.serial.reset_tx_fifo_empty_interrupt();
+
+        // Stop refilling once the buffer is drained. The TX half enables the
+        // interrupt again after writing to the buffer, checking it in a
+        // critical section makes sure that such a write isn't missed.
+        critical_section::with(|_| {
+            if self.tx.is_empty() {
+                self.serial.unlisten_tx_fifo_empty();
+            }
+        });
+    }
+}
+
+/// TX half of a [`BufferedSerial`]
+///
+/// The data is sent by [`BufferedSerialHandler::handle_interrupt`]. In RS-485
+/// mode the handler also releases RTS, so flushing only waits for the
+/// transmission to finish.
+pub struct BufferedTx<'d> {
+    registers: &'static RegisterBlock,
+    buffer: Producer<'d>,
+}
+
+impl<'d> BufferedTx<'d> {
+    fn write_byte(&mut self, word: u8) -> nb::Result<(), Error> {
+        if !self.buffer.push(word) {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // let the interrupt handler refill the TX FIFO
+        critical_section::with(|_| {
+            self.registers
+                .int_ena
+                .modify(|_, w| w.txfifo_empty_int_ena().set_bit())
+        });
+
+        Ok(())
+    }
+
+    fn flush_tx(&mut self) -> nb::Result<(), Error> {
+        let fifo_empty = self.registers.status.read().txfifo_cnt().bits() == 0;
+
+        if self.buffer.is_empty() && fifo_empty && is_tx_idle(self.registers) {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+/// RX half of a [`BufferedSerial`]
+///
+/// Returns the data moved to the RX buffer by
+/// [`BufferedSerialHandler::handle_interrupt`].
+pub struct BufferedRx<'d> {
+    buffer: Consumer<'d>,
+}
+
+impl<'d> BufferedRx<'d> {
+    fn read_byte(&mut self) -> nb::Result<u8, Error> {
+        self.buffer.pop().ok_or(nb::Error::WouldBlock)
+    }
+}
+
+impl<'d, T> embedded_hal::serial::Write<u8> for BufferedSerial<'d, T>
+where
+    T: Instance,
+{
+    type Error = Error;
+
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.write_byte(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.flush_tx()
+    }
+}
+
+impl<'d, T> embedded_hal::serial::Read<u8> for BufferedSerial<'d, T>
+where
+    T: Instance,
+{
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.read_byte()
+    }
+}
+
+impl<'d> embedded_hal::serial::Write<u8> for BufferedTx<'d> {
+    type Error = Error;
+
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.write_byte(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.flush_tx()
+    }
+}
+
+impl<'d> embedded_hal::serial::Read<u8> for BufferedRx<'d> {
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.read_byte()
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<'d, T> embedded_hal_1::serial::ErrorType for BufferedSerial<'d, T> {
+    type Error = Error;
+}
+
+#[cfg(feature = "eh1")]
+impl<'d, T> embedded_hal_nb::serial::Read for BufferedSerial<'d, T>
+where
+    T: Instance,
+{
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.read_byte()
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<'d, T> embedded_hal_nb::serial::Write for BufferedSerial<'d, T>
+where
+    T: Instance,
+{
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.write_byte(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.flush_tx()
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<'d> embedded_hal_1::serial::ErrorType for BufferedTx<'d> {
+    type Error = Error;
+}
+
+#[cfg(feature = "eh1")]
+impl<'d> embedded_hal_nb::serial::Write for BufferedTx<'d> {
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.write_byte(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.flush_tx()
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<'d> embedded_hal_1::serial::ErrorType for BufferedRx<'d> {
+    type Error = Error;
+}
+
+#[cfg(feature = "eh1")]
+impl<'d> embedded_hal_nb::serial::Read for BufferedRx<'d> {
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.read_byte()
+    }
+}
@@ -24,6 +24,11 @@
 //! );
 //! ```
 //!
+//! The bus frequency, the SPI mode, the bit order and the size of the data
+//! frames can be changed at runtime with [`Spi::change_bus_frequency`],
+//! [`Spi::set_data_mode`], [`Spi::set_bit_order`] and [`Spi::set_word_size`].
+//! Frames of 9 to 32 bits are transferred as [`u16`] or [`u32`] words.
+//!
 //! ## Exclusive access to the SPI bus
 //!
 //! If all you want to do is to communicate to a single device, and you initiate
@@ -46,7 +51,36 @@
 //! line, you may want to have a look at the [`SpiBusController`] and
 //! [`SpiBusDevice`] implemented here. These give exclusive access to the
 //! underlying SPI bus by means of a Mutex. This ensures that device
-//! transactions do not interfere with each other.
+//! transactions do not interfere with each other. Devices which need a
+//! different mode, frequency, CS polarity or CS timing are added with
+//! [`SpiBusController::add_device_with_config`], their [`SpiDeviceConfig`] is
+//! applied whenever they lock the bus.
+//!
+//! Alternatively the CS lines of the devices can be driven by the peripheral
+//! itself. Connect them to the hardware CS lines with [`Spi::attach_cs`] and
+//! choose the device before each transaction with [`Spi::select_cs`].
+//!
+//! ## Half-duplex transfers
+//!
+//! Devices like SPI flash chips expect a command, an address and a number of
+//! dummy cycles before the data, which may be sent on two or four lines. Create
+//! the instance with [`Spi::new_half_duplex`] or [`Spi::new_half_duplex_quad`]
+//! and use [`Spi::half_duplex_read`] and [`Spi::half_duplex_write`] for such
+//! transfers.
+//!
+//! ## Slave mode
+//!
+//! To let an external master drive the bus, create an [`SpiSlave`] instead.
+//! Data to send is loaded with [`SpiSlave::start_transfer`] before the master
+//! selects the slave, [`SpiSlave::poll_transfer`] returns the received data
+//! once the master has deasserted CS again.
+//!
+//! ## DMA
+//!
+//! For larger amounts of data the SPI instance can be combined with a DMA
+//! channel via [`Spi::with_dma`]. The resulting [`SpiDma`] moves whole buffers
+//! without CPU intervention, see [`SpiDma::dma_write`], [`SpiDma::dma_read`]
+//! and [`SpiDma::dma_transfer`].
 
 use core::convert::Infallible;
 
@@ -54,6 +88,7 @@ use fugit::HertzU32;
 
 use crate::{
     clock::Clocks,
+    dma::DmaError,
     pac::spi2::RegisterBlock,
     system::PeripheralClockControl,
     types::{InputSignal, OutputSignal},
@@ -77,8 +112,150 @@ pub enum SpiMode {
     Mode3,
 }
 
+/// Order in which the bits of a word are shifted out and in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpiBitOrder {
+    /// The most significant bit first (default)
+    MSBFirst,
+    /// The least significant bit first
+    LSBFirst,
+}
+
+/// Word types which can be transferred by an SPI instance
+///
+/// Words are transferred as frames of the size configured with
+/// [`Spi::set_word_size`], bits of a word beyond that size are ignored. If the
+/// configured size exceeds the width of the word type, frames are as wide as
+/// the word type.
+pub trait Word: Copy {
+    /// Width of the word type in bits
+    const BITS: u8;
+
+    #[doc(hidden)]
+    fn into_u32(self) -> u32;
+
+    #[doc(hidden)]
+    fn from_u32(value: u32) -> Self;
+}
+
+impl Word for u8 {
+    const BITS: u8 = 8;
+
+    fn into_u32(self) -> u32 {
+        self as u32
+    }
+
+    fn from_u32(value: u32) -> Self {
+        value as u8
+    }
+}
+
+impl Word for u16 {
+    const BITS: u8 = 16;
+
+    fn into_u32(self) -> u32 {
+        self as u32
+    }
+
+    fn from_u32(value: u32) -> Self {
+        value as u16
+    }
+}
+
+impl Word for u32 {
+    const BITS: u8 = 32;
+
+    fn into_u32(self) -> u32 {
+        self
+    }
+
+    fn from_u32(value: u32) -> Self {
+        value
+    }
+}
+
+/// SPI errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Error {
+    /// The DMA channel could not be set up
+    DmaError(DmaError),
+    /// The buffer is larger than the SPI peripheral can transfer at once
+    MaxDmaTransferSizeExceeded,
+    /// The data of a half-duplex transfer does not fit into the FIFO
+    FifoSizeExceeded,
+    /// The length of a command or address phase is out of range
+    InvalidPhaseLength,
+    /// The combination of data modes is not supported by the peripheral
+    Unsupported,
+    /// The word size is out of range
+    InvalidWordSize,
+}
+
+impl From<DmaError> for Error {
+    fn from(value: DmaError) -> Self {
+        Error::DmaError(value)
+    }
+}
+
+/// Hardware CS line of an SPI peripheral
+///
+/// SPI2 of the ESP32-C3, ESP32-S2 and ESP32-S3 has six lines, all other SPI
+/// peripherals have three.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChipSelect {
+    Cs0,
+    Cs1,
+    Cs2,
+    Cs3,
+    Cs4,
+    Cs5,
+}
+
+/// Number of lines used by a phase of a half-duplex transfer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpiDataMode {
+    /// One line (MOSI or MISO)
+    Single,
+    /// Two lines (SIO0 and SIO1)
+    Dual,
+    /// Four lines (SIO0 to SIO3)
+    Quad,
+}
+
+/// Command phase of a half-duplex transfer
+#[derive(Debug, Clone, Copy)]
+pub struct Command {
+    /// The command, sent MSB first
+    pub value: u16,
+    /// The number of bits (1 to 16)
+    pub length: u8,
+    /// The lines used to send the command
+    pub mode: SpiDataMode,
+}
+
+/// Address phase of a half-duplex transfer
+#[derive(Debug, Clone, Copy)]
+pub struct Address {
+    /// The address, sent MSB first
+    pub value: u32,
+    /// The number of bits (1 to 32)
+    pub length: u8,
+    /// The lines used to send the address
+    pub mode: SpiDataMode,
+}
+
 pub struct Spi<T> {
     spi: T,
+    settings: SpiSettings,
+}
+
+/// Settings of an SPI instance which can't be read back from the peripheral
+#[derive(Debug, Clone, Copy)]
+struct SpiSettings {
+    word_size: u8,
+    mode: SpiMode,
+    frequency: HertzU32,
+    apb_clock: HertzU32,
 }
 
 impl<T> Spi<T>
@@ -173,6 +350,46 @@ where
         Self::new_internal(spi, frequency, mode, peripheral_clock_control, clocks)
     }
 
+    /// Constructs an SPI instance for half-duplex transfers on up to two
+    /// data lines.
+    ///
+    /// SIO0 (MOSI) and SIO1 (MISO) are used in both directions, depending on
+    /// the phase of the transfer.
+    pub fn new_half_duplex<
+        SCK: OutputPin,
+        SIO0: OutputPin + InputPin,
+        SIO1: OutputPin + InputPin,
+        CS: OutputPin,
+    >(
+        spi: T,
+        mut sck: SCK,
+        mut sio0: SIO0,
+        mut sio1: SIO1,
+        mut cs: CS,
+        frequency: HertzU32,
+        mode: SpiMode,
+        peripheral_clock_control: &mut PeripheralClockControl,
+        clocks: &Clocks,
+    ) -> Self {
+        sck.set_to_push_pull_output()
+            .connect_peripheral_to_output(spi.sclk_signal());
+
+        sio0.set_to_push_pull_output()
+            .connect_peripheral_to_output(spi.mosi_signal())
+            .enable_input(true)
+            .connect_input_to_peripheral(spi.sio0_input_signal());
+
+        sio1.set_to_push_pull_output()
+            .connect_peripheral_to_output(spi.sio1_output_signal())
+            .enable_input(true)
+            .connect_input_to_peripheral(spi.miso_signal());
+
+        cs.set_to_push_pull_output()
+            .connect_peripheral_to_output(spi.cs_signal());
+
+        Self::new_internal(spi, frequency, mode, peripheral_clock_control, clocks)
+    }
+
     pub fn new_internal(
         spi: T,
         frequency: HertzU32,
@@ -182,20 +399,236 @@ where
     ) -> Self {
         spi.enable_peripheral(peripheral_clock_control);
 
-        let mut spi = Self { spi };
-        spi.spi.setup(frequency, clocks);
+        let mut spi = Self {
+            spi,
+            settings: SpiSettings {
+                word_size: 8,
+                mode,
+                frequency,
+                apb_clock: clocks.apb_clock,
+            },
+        };
+        spi.spi.setup(frequency, clocks.apb_clock);
         spi.spi.init();
         spi.spi.set_data_mode(mode);
+        // `init` enables all CS lines, only drive the first one
+        spi.spi.select_cs(ChipSelect::Cs0);
 
         spi
     }
 
+    /// Change the bus frequency of the SPI instance
+    pub fn change_bus_frequency(&mut self, frequency: HertzU32, clocks: &Clocks) {
+        self.settings.frequency = frequency;
+        self.settings.apb_clock = clocks.apb_clock;
+        self.spi.setup(frequency, clocks.apb_clock);
+    }
+
+    /// Change the clock polarity and phase of the SPI instance
+    pub fn set_data_mode(&mut self, mode: SpiMode) {
+        self.settings.mode = mode;
+        self.spi.set_data_mode(mode);
+    }
+
+    /// Change the order in which bits are received and sent
+    pub fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+        self.spi.set_bit_order(read_order, write_order);
+    }
+
+    /// Connect `pin` to a hardware CS line of the peripheral
+    ///
+    /// The pin is driven by the peripheral whenever `line` is selected with
+    /// [`Spi::select_cs`]. Returns [`Error::Unsupported`] if the peripheral
+    /// doesn't have the line.
+    pub fn attach_cs<CS: OutputPin>(&mut self, line: ChipSelect, mut pin: CS) -> Result<(), Error> {
+        let signal = *self
+            .spi
+            .cs_signals()
+            .get(line as usize)
+            .ok_or(Error::Unsupported)?;
+
+        pin.set_to_push_pull_output()
+            .connect_peripheral_to_output(signal);
+
+        Ok(())
+    }
+
+    /// Select the hardware CS line asserted by the following transfers
+    ///
+    /// Lines which are not selected stay deasserted. Instances start with
+    /// [`ChipSelect::Cs0`] selected, which is the line of the CS pin passed to
+    /// the constructor.
+    pub fn select_cs(&mut self, line: ChipSelect) -> Result<(), Error> {
+        if line as usize >= self.spi.cs_signals().len() {
+            return Err(Error::Unsupported);
+        }
+
+        self.spi.select_cs(line);
+
+        Ok(())
+    }
+
+    /// Change the setup and hold times of the hardware CS lines
+    ///
+    /// CS is asserted at least `setup_cycles` SPI clock cycles before the first
+    /// clock edge and deasserted at least `hold_cycles` cycles after the last
+    /// one.
+    pub fn set_cs_timing(&mut self, setup_cycles: u8, hold_cycles: u8) {
+        self.spi.set_cs_timing(setup_cycles, hold_cycles);
+    }
+
+    /// Change the size of the data frames to `bits` (8 to 32)
+    ///
+    /// The size applies to transfers of [`u16`] and [`u32`] words, transfers of
+    /// bytes always use 8 bit frames. See [`Word`] for details.
+    pub fn set_word_size(&mut self, bits: u8) -> Result<(), Error> {
+        if !(8..=32).contains(&bits) {
+            return Err(Error::InvalidWordSize);
+        }
+
+        self.settings.word_size = bits;
+
+        Ok(())
+    }
+
+    /// The size of the frames used to transfer words of type `W`
+    fn frame_size<W: Word>(&self) -> u8 {
+        u8::min(self.settings.word_size, W::BITS)
+    }
+
+    /// Read `buffer.len()` bytes in a half-duplex transfer
+    ///
+    /// The optional command and address phases and `dummy` dummy cycles
+    /// precede the data, which is received on the lines given by `data_mode`.
+    /// At most one FIFO worth of data (64 bytes, 72 on the ESP32-S2) can be
+    /// read at once. Using more than one line requires an instance created by
+    /// [`Spi::new_half_duplex`] or [`Spi::new_half_duplex_quad`].
+    pub fn half_duplex_read(
+        &mut self,
+        data_mode: SpiDataMode,
+        cmd: Option<Command>,
+        address: Option<Address>,
+        dummy: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        self.spi
+            .setup_half_duplex(false, buffer.len(), data_mode, cmd, address, dummy)?;
+
+        self.spi.update();
+        self.spi
+            .register_block()
+            .cmd
+            .modify(|_, w| w.usr().set_bit());
+        self.spi.flush().ok();
+
+        self.spi.read_bytes_from_fifo(buffer).ok();
+        self.spi.end_half_duplex();
+
+        Ok(())
+    }
+
+    /// Write `buffer` in a half-duplex transfer
+    ///
+    /// The optional command and address phases and `dummy` dummy cycles
+    /// precede the data, which is sent on the lines given by `data_mode`. At
+    /// most one FIFO worth of data (64 bytes, 72 on the ESP32-S2) can be
+    /// written at once. Using more than one line requires an instance created
+    /// by [`Spi::new_half_duplex`] or [`Spi::new_half_duplex_quad`].
+    pub fn half_duplex_write(
+        &mut self,
+        data_mode: SpiDataMode,
+        cmd: Option<Command>,
+        address: Option<Address>,
+        dummy: u8,
+        buffer: &[u8],
+    ) -> Result<(), Error> {
+        self.spi
+            .setup_half_duplex(true, buffer.len(), data_mode, cmd, address, dummy)?;
+
+        let fifo_ptr = self.spi.register_block().w0.as_ptr();
+        for (index, chunk) in buffer.chunks(4).enumerate() {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+
+            unsafe {
+                fifo_ptr.add(index).write_volatile(u32::from_le_bytes(word));
+            }
+        }
+
+        self.spi.update();
+        self.spi
+            .register_block()
+            .cmd
+            .modify(|_, w| w.usr().set_bit());
+        self.spi.flush().ok();
+
+        self.spi.end_half_duplex();
+
+        Ok(())
+    }
+
     /// Return the raw interface to the underlying peripheral instance
     pub fn free(self) -> T {
         self.spi
     }
 }
 
+impl<T> Spi<T>
+where
+    T: QuadInstance,
+{
+    /// Constructs an SPI instance for half-duplex transfers on up to four
+    /// data lines.
+    ///
+    /// All SIO pins are used in both directions, depending on the phase of the
+    /// transfer.
+    ///
+    /// Only peripherals with four data lines implement [`QuadInstance`], so
+    /// this is not available for SPI3 of the ESP32-S2.
+    pub fn new_half_duplex_quad<
+        SCK: OutputPin,
+        SIO0: OutputPin + InputPin,
+        SIO1: OutputPin + InputPin,
+        SIO2: OutputPin + InputPin,
+        SIO3: OutputPin + InputPin,
+        CS: OutputPin,
+    >(
+        spi: T,
+        sck: SCK,
+        sio0: SIO0,
+        sio1: SIO1,
+        mut sio2: SIO2,
+        mut sio3: SIO3,
+        cs: CS,
+        frequency: HertzU32,
+        mode: SpiMode,
+        peripheral_clock_control: &mut PeripheralClockControl,
+        clocks: &Clocks,
+    ) -> Self {
+        sio2.set_to_push_pull_output()
+            .connect_peripheral_to_output(spi.sio2_output_signal())
+            .enable_input(true)
+            .connect_input_to_peripheral(spi.sio2_input_signal());
+
+        sio3.set_to_push_pull_output()
+            .connect_peripheral_to_output(spi.sio3_output_signal())
+            .enable_input(true)
+            .connect_input_to_peripheral(spi.sio3_input_signal());
+
+        Self::new_half_duplex(
+            spi,
+            sck,
+            sio0,
+            sio1,
+            cs,
+            frequency,
+            mode,
+            peripheral_clock_control,
+            clocks,
+        )
+    }
+}
+
 impl<T> embedded_hal::spi::FullDuplex<u8> for Spi<T>
 where
     T: Instance,
@@ -235,13 +668,69 @@ where
     }
 }
 
+macro_rules! impl_word_traits {
+    ($($word:ty),+) => {
+        $(
+            impl<T> embedded_hal::spi::FullDuplex<$word> for Spi<T>
+            where
+                T: Instance,
+            {
+                type Error = Infallible;
+
+                fn read(&mut self) -> nb::Result<$word, Self::Error> {
+                    let bits = self.frame_size::<$word>();
+                    self.spi.read_word(bits)
+                }
+
+                fn send(&mut self, word: $word) -> nb::Result<(), Self::Error> {
+                    let bits = self.frame_size::<$word>();
+                    self.spi.write_word(word, bits)
+                }
+            }
+
+            impl<T> embedded_hal::blocking::spi::Transfer<$word> for Spi<T>
+            where
+                T: Instance,
+            {
+                type Error = Infallible;
+
+                fn transfer<'w>(
+                    &mut self,
+                    words: &'w mut [$word],
+                ) -> Result<&'w [$word], Self::Error> {
+                    let bits = self.frame_size::<$word>();
+                    self.spi.transfer_words(words, bits)?;
+                    Ok(words)
+                }
+            }
+
+            impl<T> embedded_hal::blocking::spi::Write<$word> for Spi<T>
+            where
+                T: Instance,
+            {
+                type Error = Infallible;
+
+                fn write(&mut self, words: &[$word]) -> Result<(), Self::Error> {
+                    let bits = self.frame_size::<$word>();
+                    self.spi.write_words(words, bits)?;
+                    self.spi.flush()?;
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+impl_word_traits!(u16, u32);
+
 #[cfg(feature = "eh1")]
 pub use ehal1::*;
 
 #[cfg(feature = "eh1")]
 mod ehal1 {
-    use core::cell::RefCell;
+    use core::cell::{Cell, RefCell};
 
+    use critical_section::CriticalSection;
     use embedded_hal_1::spi::{
         self,
         ErrorType,
@@ -375,6 +864,157 @@ mod ehal1 {
         }
     }
 
+    macro_rules! impl_word_traits_eh1 {
+        ($($word:ty),+) => {
+            $(
+                impl<T> FullDuplex<$word> for Spi<T>
+                where
+                    T: Instance,
+                {
+                    fn read(&mut self) -> nb::Result<$word, Self::Error> {
+                        let bits = self.frame_size::<$word>();
+                        self.spi.read_word(bits)
+                    }
+
+                    fn write(&mut self, word: $word) -> nb::Result<(), Self::Error> {
+                        let bits = self.frame_size::<$word>();
+                        self.spi.write_word(word, bits)
+                    }
+                }
+
+                impl<T> SpiBusWrite<$word> for Spi<T>
+                where
+                    T: Instance,
+                {
+                    fn write(&mut self, words: &[$word]) -> Result<(), Self::Error> {
+                        let bits = self.frame_size::<$word>();
+                        self.spi.write_words(words, bits)
+                    }
+                }
+
+                impl<T> SpiBusRead<$word> for Spi<T>
+                where
+                    T: Instance,
+                {
+                    fn read(&mut self, words: &mut [$word]) -> Result<(), Self::Error> {
+                        let bits = self.frame_size::<$word>();
+                        self.spi.read_words(words, bits)
+                    }
+                }
+
+                impl<T> SpiBus<$word> for Spi<T>
+                where
+                    T: Instance,
+                {
+                    fn transfer(
+                        &mut self,
+                        read: &mut [$word],
+                        write: &[$word],
+                    ) -> Result<(), Self::Error> {
+                        let bits = self.frame_size::<$word>();
+                        let chunk_size = FIFO_SIZE * 8 / bits as usize;
+                        let len = usize::max(read.len(), write.len());
+                        let mut buffer = [0 as $word; FIFO_SIZE];
+
+                        for offset in (0..len).step_by(chunk_size) {
+                            let chunk = &mut buffer[..usize::min(chunk_size, len - offset)];
+                            for (index, word) in chunk.iter_mut().enumerate() {
+                                *word = write
+                                    .get(offset + index)
+                                    .copied()
+                                    .unwrap_or(EMPTY_WRITE_PAD as $word);
+                            }
+
+                            self.spi.transfer_words(chunk, bits)?;
+
+                            for (word, received) in read.iter_mut().skip(offset).zip(chunk.iter()) {
+                                *word = *received;
+                            }
+                        }
+
+                        Ok(())
+                    }
+
+                    fn transfer_in_place(
+                        &mut self,
+                        words: &mut [$word],
+                    ) -> Result<(), Self::Error> {
+                        let bits = self.frame_size::<$word>();
+                        self.spi.transfer_words(words, bits)
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_word_traits_eh1!(u16, u32);
+
+    /// Configuration of a device on a shared SPI bus
+    ///
+    /// Applied by the [`SpiBusController`] whenever a [`SpiBusDevice`] locks
+    /// the bus.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct SpiDeviceConfig {
+        /// SPI mode of the device. `None` uses the mode the bus was set up
+        /// with.
+        pub mode: Option<SpiMode>,
+        /// Bus frequency of the device. `None` uses the frequency the bus was
+        /// set up with.
+        pub frequency: Option<HertzU32>,
+        /// Whether CS is asserted by driving it high instead of low
+        pub cs_active_high: bool,
+        /// Minimum number of SPI clock cycles between asserting CS and the
+        /// first clock edge
+        pub cs_setup_cycles: u8,
+        /// Minimum number of SPI clock cycles between the last clock edge and
+        /// deasserting CS
+        pub cs_hold_cycles: u8,
+    }
+
+    /// An SPI bus which can be reconfigured for each device on a
+    /// [`SpiBusController`]
+    pub trait ConfigurableSpiBus {
+        /// The current mode and frequency of the bus
+        fn bus_config(&self) -> SpiDeviceConfig;
+
+        /// Apply the configuration of a device, fields which are `None` are
+        /// left unchanged
+        fn apply_device_config(&mut self, config: &SpiDeviceConfig);
+    }
+
+    impl<T> ConfigurableSpiBus for Spi<T>
+    where
+        T: Instance,
+    {
+        fn bus_config(&self) -> SpiDeviceConfig {
+            SpiDeviceConfig {
+                mode: Some(self.settings.mode),
+                frequency: Some(self.settings.frequency),
+                ..SpiDeviceConfig::default()
+            }
+        }
+
+        fn apply_device_config(&mut self, config: &SpiDeviceConfig) {
+            if let Some(mode) = config.mode {
+                self.set_data_mode(mode);
+            }
+
+            if let Some(frequency) = config.frequency {
+                if frequency != self.settings.frequency {
+                    self.settings.frequency = frequency;
+                    self.spi.setup(frequency, self.settings.apb_clock);
+                }
+            }
+
+            // CS is driven by the device, but is asserted before the transfer
+            // starts and deasserted after it has finished. Delaying the start
+            // and the end of the transfer in hardware therefore guarantees the
+            // setup and hold times.
+            self.spi
+                .set_cs_timing(config.cs_setup_cycles, config.cs_hold_cycles);
+        }
+    }
+
     /// SPI bus controller.
     ///
     /// Has exclusive access to an SPI bus, which is managed via a `Mutex`. Used
@@ -382,8 +1022,14 @@ mod ehal1 {
     /// wrapped [`RefCell`] is used solely to achieve interior mutability.
     pub struct SpiBusController<B: SpiBus + ErrorType> {
         lock: critical_section::Mutex<RefCell<B>>,
+        // Set once the first device with its own configuration is added: the
+        // function applying a device configuration and the mode and frequency
+        // the bus was set up with, which are restored for all other devices
+        config: critical_section::Mutex<Cell<Option<BusConfig<B>>>>,
     }
 
+    type BusConfig<B> = (fn(&mut B, &SpiDeviceConfig), SpiDeviceConfig);
+
     impl<B: SpiBus + ErrorType> SpiBusController<B> {
         /// Create a new controller from an SPI bus instance.
         ///
@@ -392,11 +1038,51 @@ mod ehal1 {
         pub fn from_spi(bus: B) -> Self {
             SpiBusController {
                 lock: critical_section::Mutex::new(RefCell::new(bus)),
+                config: critical_section::Mutex::new(Cell::new(None)),
             }
         }
 
         pub fn add_device<'a, CS: OutputPin>(&'a self, cs: CS) -> SpiBusDevice<'a, B, CS> {
-            SpiBusDevice { bus: self, cs }
+            SpiBusDevice::new(self, cs)
+        }
+
+        /// Add a device which needs its own configuration of the bus
+        ///
+        /// From then on the configuration of the bus is applied before every
+        /// transaction, so that devices without their own mode or frequency
+        /// get the ones the bus was set up with.
+        pub fn add_device_with_config<'a, CS: OutputPin>(
+            &'a self,
+            cs: CS,
+            config: SpiDeviceConfig,
+        ) -> SpiBusDevice<'a, B, CS>
+        where
+            B: ConfigurableSpiBus,
+        {
+            critical_section::with(|section| {
+                let bus_config = self.config.borrow(section);
+                if bus_config.get().is_none() {
+                    let apply: fn(&mut B, &SpiDeviceConfig) = B::apply_device_config;
+                    let defaults = self.lock.borrow_ref(section).bus_config();
+                    bus_config.set(Some((apply, defaults)));
+                }
+            });
+
+            SpiBusDevice::new_with_config(self, cs, config)
+        }
+
+        // Applies the configuration of a device, if any device needs one
+        fn configure(&self, bus: &mut B, config: &SpiDeviceConfig, cs: CriticalSection<'_>) {
+            if let Some((apply, defaults)) = self.config.borrow(cs).get() {
+                apply(
+                    bus,
+                    &SpiDeviceConfig {
+                        mode: config.mode.or(defaults.mode),
+                        frequency: config.frequency.or(defaults.frequency),
+                        ..*config
+                    },
+                );
+            }
         }
     }
 
@@ -419,6 +1105,7 @@ mod ehal1 {
     {
         bus: &'a SpiBusController<B>,
         cs: CS,
+        config: SpiDeviceConfig,
     }
 
     impl<'a, B, CS> SpiBusDevice<'a, B, CS>
@@ -427,7 +1114,18 @@ mod ehal1 {
         CS: OutputPin,
     {
         pub fn new(bus: &'a SpiBusController<B>, cs: CS) -> Self {
-            SpiBusDevice { bus, cs }
+            Self::new_with_config(bus, cs, SpiDeviceConfig::default())
+        }
+
+        pub fn new_with_config(
+            bus: &'a SpiBusController<B>,
+            mut cs: CS,
+            config: SpiDeviceConfig,
+        ) -> Self {
+            cs.set_to_push_pull_output()
+                .set_output_high(!config.cs_active_high);
+
+            SpiBusDevice { bus, cs, config }
         }
     }
 
@@ -452,15 +1150,18 @@ mod ehal1 {
         ) -> Result<R, Self::Error> {
             critical_section::with(|cs| {
                 let mut bus = self.bus.lock.borrow_ref_mut(cs);
+                self.bus.configure(&mut bus, &self.config, cs);
 
-                self.cs.set_to_push_pull_output().set_output_high(false);
+                self.cs
+                    .set_to_push_pull_output()
+                    .set_output_high(self.config.cs_active_high);
 
                 // We postpone handling these errors until AFTER we raised CS again, so the bus
                 // is free (Or we die trying if CS errors).
                 let f_res = f(&mut bus);
                 let flush_res = bus.flush();
 
-                self.cs.set_output_high(true);
+                self.cs.set_output_high(!self.config.cs_active_high);
 
                 let f_res = f_res.map_err(|_| spi::ErrorKind::Other)?;
                 flush_res.map_err(|_| spi::ErrorKind::Other)?;
@@ -471,53 +1172,786 @@ mod ehal1 {
     }
 }
 
-pub trait Instance {
-    fn register_block(&self) -> &RegisterBlock;
+pub use self::dma::*;
 
-    fn sclk_signal(&self) -> OutputSignal;
+mod dma {
+    use core::mem;
 
-    fn mosi_signal(&self) -> OutputSignal;
+    use embedded_dma::{ReadBuffer, WriteBuffer};
 
-    fn miso_signal(&self) -> InputSignal;
+    use super::*;
+    #[cfg(not(esp32c3))]
+    use crate::dma::Spi3Peripheral;
+    use crate::dma::{
+        Channel,
+        DmaPeripheral,
+        DmaTransfer,
+        PeripheralMarker,
+        Rx,
+        Spi2Peripheral,
+        Tx,
+    };
 
-    fn cs_signal(&self) -> OutputSignal;
+    impl<T> Spi<T>
+    where
+        T: Instance,
+    {
+        /// Use a DMA channel for transfers
+        ///
+        /// The channel needs to be suitable for this SPI peripheral.
+        pub fn with_dma<TX, RX, P>(self, channel: Channel<TX, RX, P>) -> SpiDma<T, TX, RX, P>
+        where
+            T: InstanceDma<P>,
+            TX: Tx,
+            RX: Rx,
+            P: PeripheralMarker,
+        {
+            SpiDma {
+                spi: self.spi,
+                settings: self.settings,
+                channel,
+            }
+        }
+    }
 
-    fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl);
+    /// An SPI instance which transfers data via DMA
+    pub struct SpiDma<T, TX, RX, P>
+    where
+        T: InstanceDma<P>,
+        TX: Tx,
+        RX: Rx,
+        P: PeripheralMarker,
+    {
+        spi: T,
+        settings: SpiSettings,
+        channel: Channel<TX, RX, P>,
+    }
 
-    fn init(&mut self) {
-        let reg_block = self.register_block();
-        reg_block.user.modify(|_, w| {
-            w.usr_miso_highpart()
-                .clear_bit()
-                .usr_miso_highpart()
-                .clear_bit()
-                .doutdin()
-                .set_bit()
-                .usr_miso()
-                .set_bit()
-                .usr_mosi()
-                .set_bit()
-                .cs_hold()
-                .set_bit()
-                .usr_dummy_idle()
-                .set_bit()
-                .usr_addr()
-                .clear_bit()
-                .usr_command()
-                .clear_bit()
-        });
+    impl<T, TX, RX, P> SpiDma<T, TX, RX, P>
+    where
+        T: InstanceDma<P>,
+        TX: Tx,
+        RX: Rx,
+        P: PeripheralMarker,
+    {
+        /// Write the bytes of `words` without waiting for the transfer to
+        /// finish
+        ///
+        /// The buffer and the SPI instance are returned once the transfer is
+        /// done.
+        pub fn dma_write<TXBUF>(
+            mut self,
+            words: TXBUF,
+        ) -> Result<SpiDmaTransfer<T, TX, RX, P, TXBUF>, (Error, Self, TXBUF)>
+        where
+            TXBUF: ReadBuffer<Word = u8>,
+        {
+            let (ptr, len) = unsafe { words.read_buffer() };
+
+            match self.spi.start_transfer_dma(
+                Some((ptr, len)),
+                None,
+                &mut self.channel.tx,
+                &mut self.channel.rx,
+            ) {
+                Ok(()) => Ok(SpiDmaTransfer {
+                    spi_dma: self,
+                    buffer: words,
+                    reading: false,
+                }),
+                Err(err) => Err((err, self, words)),
+            }
+        }
 
-        #[cfg(not(any(esp32, esp32s2)))]
-        reg_block.clk_gate.modify(|_, w| {
-            w.clk_en()
-                .set_bit()
-                .mst_clk_active()
-                .set_bit()
-                .mst_clk_sel()
-                .set_bit()
-        });
+        /// Read bytes into `words` without waiting for the transfer to finish
+        ///
+        /// The buffer and the SPI instance are returned once the transfer is
+        /// done. On the ESP32 and ESP32-S2 the buffer needs to be word aligned
+        /// and its length a multiple of 4.
+        pub fn dma_read<RXBUF>(
+            mut self,
+            mut words: RXBUF,
+        ) -> Result<SpiDmaTransfer<T, TX, RX, P, RXBUF>, (Error, Self, RXBUF)>
+        where
+            RXBUF: WriteBuffer<Word = u8>,
+        {
+            let (ptr, len) = unsafe { words.write_buffer() };
+
+            match self.spi.start_transfer_dma(
+                None,
+                Some((ptr, len)),
+                &mut self.channel.tx,
+                &mut self.channel.rx,
+            ) {
+                Ok(()) => Ok(SpiDmaTransfer {
+                    spi_dma: self,
+                    buffer: words,
+                    reading: true,
+                }),
+                Err(err) => Err((err, self, words)),
+            }
+        }
 
-        reg_block.ctrl.write(|w| unsafe { w.bits(0) });
+        /// Write the bytes of `words` and simultaneously read into
+        /// `read_buffer` without waiting for the transfer to finish
+        ///
+        /// The transfer lasts as long as the longer of both buffers. The
+        /// buffers and the SPI instance are returned once the transfer is
+        /// done.
+        pub fn dma_transfer<TXBUF, RXBUF>(
+            mut self,
+            words: TXBUF,
+            mut read_buffer: RXBUF,
+        ) -> Result<SpiDmaTransfer<T, TX, RX, P, (TXBUF, RXBUF)>, (Error, Self, (TXBUF, RXBUF))>
+        where
+            TXBUF: ReadBuffer<Word = u8>,
+            RXBUF: WriteBuffer<Word = u8>,
+        {
+            let (write_ptr, write_len) = unsafe { words.read_buffer() };
+            let (read_ptr, read_len) = unsafe { read_buffer.write_buffer() };
+
+            match self.spi.start_transfer_dma(
+                Some((write_ptr, write_len)),
+                Some((read_ptr, read_len)),
+                &mut self.channel.tx,
+                &mut self.channel.rx,
+            ) {
+                Ok(()) => Ok(SpiDmaTransfer {
+                    spi_dma: self,
+                    buffer: (words, read_buffer),
+                    reading: true,
+                }),
+                Err(err) => Err((err, self, (words, read_buffer))),
+            }
+        }
+
+        /// Return the SPI instance and the DMA channel
+        pub fn free(self) -> (Spi<T>, Channel<TX, RX, P>) {
+            self.spi.disable_dma();
+
+            (
+                Spi {
+                    spi: self.spi,
+                    settings: self.settings,
+                },
+                self.channel,
+            )
+        }
+    }
+
+    /// An in-progress DMA transfer of an SPI instance
+    pub struct SpiDmaTransfer<T, TX, RX, P, BUFFER>
+    where
+        T: InstanceDma<P>,
+        TX: Tx,
+        RX: Rx,
+        P: PeripheralMarker,
+    {
+        spi_dma: SpiDma<T, TX, RX, P>,
+        buffer: BUFFER,
+        reading: bool,
+    }
+
+    impl<T, TX, RX, P, BUFFER> SpiDmaTransfer<T, TX, RX, P, BUFFER>
+    where
+        T: InstanceDma<P>,
+        TX: Tx,
+        RX: Rx,
+        P: PeripheralMarker,
+    {
+        fn block_until_done(&self) {
+            while !DmaTransfer::is_done(self) {
+                // wait
+            }
+        }
+    }
+
+    impl<T, TX, RX, P, BUFFER> DmaTransfer<BUFFER, SpiDma<T, TX, RX, P>>
+        for SpiDmaTransfer<T, TX, RX, P, BUFFER>
+    where
+        T: InstanceDma<P>,
+        TX: Tx,
+        RX: Rx,
+        P: PeripheralMarker,
+    {
+        fn is_done(&self) -> bool {
+            // The peripheral might be done before the DMA has written all
+            // received data to memory
+            !self.spi_dma.spi.is_bus_busy() && (!self.reading || self.spi_dma.channel.rx.is_done())
+        }
+
+        fn wait(self) -> (BUFFER, SpiDma<T, TX, RX, P>) {
+            self.block_until_done();
+
+            // `Drop` prevents moving out of `self`, which is forgotten
+            // afterwards so both values are only owned once
+            unsafe {
+                let buffer = core::ptr::read(&self.buffer);
+                let spi_dma = core::ptr::read(&self.spi_dma);
+                mem::forget(self);
+
+                (buffer, spi_dma)
+            }
+        }
+    }
+
+    impl<T, TX, RX, P, BUFFER> Drop for SpiDmaTransfer<T, TX, RX, P, BUFFER>
+    where
+        T: InstanceDma<P>,
+        TX: Tx,
+        RX: Rx,
+        P: PeripheralMarker,
+    {
+        fn drop(&mut self) {
+            self.block_until_done();
+        }
+    }
+
+    /// The maximum number of bytes in a single SPI transaction
+    const MAX_DMA_SIZE: usize = 32736;
+
+    /// SPI peripherals which can transfer data via DMA channels marked with
+    /// `P`
+    pub trait InstanceDma<P>: Instance
+    where
+        P: PeripheralMarker,
+    {
+        fn dma_peripheral(&self) -> DmaPeripheral;
+
+        /// Starts a transfer of the given write and/or read buffers
+        fn start_transfer_dma<TX: Tx, RX: Rx>(
+            &mut self,
+            write_buffer: Option<(*const u8, usize)>,
+            read_buffer: Option<(*mut u8, usize)>,
+            tx: &mut TX,
+            rx: &mut RX,
+        ) -> Result<(), Error> {
+            let write_len = write_buffer.map_or(0, |(_, len)| len);
+            let read_len = read_buffer.map_or(0, |(_, len)| len);
+            let len = usize::max(write_len, read_len);
+
+            if len > MAX_DMA_SIZE {
+                return Err(Error::MaxDmaTransferSizeExceeded);
+            }
+            if len == 0 {
+                return Err(Error::DmaError(DmaError::EmptyBuffer));
+            }
+
+            let reg_block = self.register_block();
+
+            self.configure_datalen(len as u32 * 8);
+            reg_block.user.modify(|_, w| {
+                w.usr_mosi()
+                    .bit(write_buffer.is_some())
+                    .usr_miso()
+                    .bit(read_buffer.is_some())
+            });
+            self.enable_dma(write_buffer.is_some(), read_buffer.is_some());
+            self.update();
+
+            self.reset_dma_before_load_dma_dscr();
+            if let Some((ptr, len)) = write_buffer {
+                tx.prepare_transfer(self.dma_peripheral(), ptr, len)?;
+            }
+            if let Some((ptr, len)) = read_buffer {
+                rx.prepare_transfer(self.dma_peripheral(), ptr, len)?;
+            }
+
+            self.clear_dma_interrupts();
+            self.reset_dma_before_usr_cmd();
+
+            reg_block.cmd.modify(|_, w| w.usr().set_bit());
+
+            Ok(())
+        }
+
+        fn is_bus_busy(&self) -> bool {
+            self.register_block().cmd.read().usr().bit_is_set()
+        }
+
+        #[cfg(not(esp32))]
+        fn enable_dma(&self, tx: bool, rx: bool) {
+            self.register_block()
+                .dma_conf
+                .modify(|_, w| w.dma_tx_ena().bit(tx).dma_rx_ena().bit(rx));
+        }
+
+        #[cfg(esp32)]
+        fn enable_dma(&self, _tx: bool, _rx: bool) {
+            // the DMA is used as soon as a descriptor list is linked
+        }
+
+        /// Restores the configuration used for transfers through the FIFO
+        fn disable_dma(&self) {
+            #[cfg(not(esp32))]
+            self.enable_dma(false, false);
+
+            #[cfg(pdma)]
+            self.reset_dma_before_load_dma_dscr();
+
+            self.register_block()
+                .user
+                .modify(|_, w| w.usr_mosi().set_bit().usr_miso().set_bit());
+        }
+
+        #[cfg(gdma)]
+        fn clear_dma_interrupts(&self) {
+            self.register_block().dma_int_clr.write(|w| {
+                w.dma_infifo_full_err_int_clr()
+                    .set_bit()
+                    .dma_outfifo_empty_err_int_clr()
+                    .set_bit()
+                    .trans_done_int_clr()
+                    .set_bit()
+                    .mst_rx_afifo_wfull_err_int_clr()
+                    .set_bit()
+                    .mst_tx_afifo_rempty_err_int_clr()
+                    .set_bit()
+            });
+        }
+
+        #[cfg(pdma)]
+        fn clear_dma_interrupts(&self) {
+            self.register_block().dma_int_clr.write(|w| {
+                w.inlink_dscr_empty_int_clr()
+                    .set_bit()
+                    .outlink_dscr_error_int_clr()
+                    .set_bit()
+                    .inlink_dscr_error_int_clr()
+                    .set_bit()
+                    .in_done_int_clr()
+                    .set_bit()
+                    .in_err_eof_int_clr()
+                    .set_bit()
+                    .in_suc_eof_int_clr()
+                    .set_bit()
+                    .out_done_int_clr()
+                    .set_bit()
+                    .out_eof_int_clr()
+                    .set_bit()
+                    .out_total_eof_int_clr()
+                    .set_bit()
+            });
+        }
+
+        #[cfg(gdma)]
+        fn reset_dma_before_load_dma_dscr(&self) {
+            // the channel is reset by the GDMA controller
+        }
+
+        #[cfg(pdma)]
+        fn reset_dma_before_load_dma_dscr(&self) {
+            let reg_block = self.register_block();
+
+            reg_block.dma_conf.modify(|_, w| {
+                w.out_rst()
+                    .set_bit()
+                    .in_rst()
+                    .set_bit()
+                    .ahbm_fifo_rst()
+                    .set_bit()
+                    .ahbm_rst()
+                    .set_bit()
+            });
+            reg_block.dma_conf.modify(|_, w| {
+                w.out_rst()
+                    .clear_bit()
+                    .in_rst()
+                    .clear_bit()
+                    .ahbm_fifo_rst()
+                    .clear_bit()
+                    .ahbm_rst()
+                    .clear_bit()
+            });
+        }
+
+        #[cfg(gdma)]
+        fn reset_dma_before_usr_cmd(&self) {
+            self.register_block().dma_conf.modify(|_, w| {
+                w.rx_afifo_rst()
+                    .set_bit()
+                    .buf_afifo_rst()
+                    .set_bit()
+                    .dma_afifo_rst()
+                    .set_bit()
+            });
+        }
+
+        #[cfg(pdma)]
+        fn reset_dma_before_usr_cmd(&self) {
+            // not needed on ESP32/ESP32S2
+        }
+    }
+
+    impl<P> InstanceDma<P> for crate::pac::SPI2
+    where
+        P: Spi2Peripheral,
+    {
+        fn dma_peripheral(&self) -> DmaPeripheral {
+            DmaPeripheral::Spi2
+        }
+    }
+
+    #[cfg(not(esp32c3))]
+    impl<P> InstanceDma<P> for crate::pac::SPI3
+    where
+        P: Spi3Peripheral,
+    {
+        fn dma_peripheral(&self) -> DmaPeripheral {
+            DmaPeripheral::Spi3
+        }
+    }
+}
+
+pub use self::slave::*;
+
+mod slave {
+    use super::*;
+
+    /// SPI peripheral operating as a slave to an external master
+    ///
+    /// A transaction starts when the master asserts CS and ends when it
+    /// deasserts it again. The data to send has to be loaded before the master
+    /// starts the transaction, at most one FIFO worth of data (64 bytes, 72
+    /// on the ESP32-S2) is exchanged per transaction.
+    pub struct SpiSlave<T> {
+        spi: T,
+    }
+
+    impl<T> SpiSlave<T>
+    where
+        T: InstanceSlave,
+    {
+        /// Constructs an SPI slave instance in 8bit dataframe mode.
+        ///
+        /// SCK, MOSI and CS are driven by the master, MISO is driven by the
+        /// slave while CS is asserted.
+        pub fn new<SCK: InputPin, MOSI: InputPin, MISO: OutputPin, CS: InputPin>(
+            spi: T,
+            mut sck: SCK,
+            mut mosi: MOSI,
+            mut miso: MISO,
+            mut cs: CS,
+            mode: SpiMode,
+            peripheral_clock_control: &mut PeripheralClockControl,
+        ) -> Self {
+            sck.set_to_input()
+                .connect_input_to_peripheral(spi.sclk_input_signal());
+
+            mosi.set_to_input()
+                .connect_input_to_peripheral(spi.sio0_input_signal());
+
+            miso.set_to_push_pull_output()
+                .connect_peripheral_to_output(spi.sio1_output_signal());
+
+            cs.set_to_input()
+                .connect_input_to_peripheral(spi.cs_input_signal());
+
+            spi.enable_peripheral(peripheral_clock_control);
+
+            let mut spi = Self { spi };
+            spi.spi.init_slave();
+            spi.spi.set_slave_data_mode(mode);
+
+            spi
+        }
+
+        /// Prepares the next transaction
+        ///
+        /// `words` are sent to the master in the next transaction, if the
+        /// master clocks more bytes than given zeros are sent. Once the master
+        /// has finished the transaction the received data can be fetched with
+        /// [`SpiSlave::poll_transfer`].
+        pub fn start_transfer(&mut self, words: &[u8]) -> Result<(), Error> {
+            if words.len() > FIFO_SIZE {
+                return Err(Error::FifoSizeExceeded);
+            }
+
+            self.spi.start_slave_transfer(words);
+
+            Ok(())
+        }
+
+        /// Checks if the master has finished the transaction prepared by
+        /// [`SpiSlave::start_transfer`]
+        ///
+        /// Returns the number of bytes clocked by the master. The received
+        /// bytes are copied to `buffer`, bytes not fitting into `buffer` are
+        /// dropped.
+        pub fn poll_transfer(&mut self, buffer: &mut [u8]) -> nb::Result<usize, Error> {
+            if !self.spi.is_slave_transfer_done() {
+                return Err(nb::Error::WouldBlock);
+            }
+
+            let received = self.spi.slave_received_bytes();
+            let len = usize::min(received, buffer.len());
+            self.spi.read_slave_buffer(&mut buffer[..len]);
+
+            Ok(received)
+        }
+
+        /// Exchanges data with the master in a single transaction
+        ///
+        /// Blocks until the master has selected and deselected the slave. See
+        /// [`SpiSlave::start_transfer`] and [`SpiSlave::poll_transfer`] for
+        /// the meaning of the arguments and the return value.
+        pub fn transfer(&mut self, words: &[u8], buffer: &mut [u8]) -> Result<usize, Error> {
+            self.start_transfer(words)?;
+            nb::block!(self.poll_transfer(buffer))
+        }
+
+        /// Return the raw interface to the underlying peripheral instance
+        pub fn free(self) -> T {
+            self.spi
+        }
+    }
+
+    pub trait InstanceSlave: Instance {
+        #[cfg(not(esp32))]
+        fn init_slave(&mut self) {
+            let reg_block = self.register_block();
+
+            reg_block.clock.write(|w| unsafe { w.bits(0) });
+            reg_block.user.write(|w| unsafe { w.bits(0) });
+            reg_block.ctrl.write(|w| unsafe { w.bits(0) });
+
+            reg_block.user.modify(|_, w| w.doutdin().set_bit());
+            reg_block.slave.modify(|_, w| w.mode().set_bit());
+
+            reg_block.dma_int_ena.write(|w| unsafe { w.bits(0) });
+            reg_block.dma_int_clr.write(|w| unsafe { w.bits(u32::MAX) });
+        }
+
+        #[cfg(esp32)]
+        fn init_slave(&mut self) {
+            let reg_block = self.register_block();
+
+            reg_block.clock.write(|w| unsafe { w.bits(0) });
+            reg_block.user.write(|w| unsafe { w.bits(0) });
+            reg_block.ctrl.write(|w| unsafe { w.bits(0) });
+
+            reg_block.user.modify(|_, w| w.doutdin().set_bit());
+            reg_block
+                .slave
+                .modify(|_, w| w.slave_mode().set_bit().wr_rd_buf_en().set_bit());
+
+            reg_block.slave.modify(|_, w| w.sync_reset().set_bit());
+            reg_block.slave.modify(|_, w| w.sync_reset().clear_bit());
+        }
+
+        #[cfg(not(esp32))]
+        fn set_slave_data_mode(&mut self, data_mode: SpiMode) {
+            let reg_block = self.register_block();
+
+            // The slave samples on the edges given by `rsck_i_edge` and
+            // shifts out data on the edges given by `tsck_i_edge`
+            let (idle_high, edge, clk_mode_13) = match data_mode {
+                SpiMode::Mode0 => (false, false, false),
+                SpiMode::Mode1 => (false, true, true),
+                SpiMode::Mode2 => (true, true, false),
+                SpiMode::Mode3 => (true, false, true),
+            };
+
+            reg_block
+                .misc
+                .modify(|_, w| w.ck_idle_edge().bit(idle_high));
+            reg_block
+                .user
+                .modify(|_, w| w.rsck_i_edge().bit(edge).tsck_i_edge().bit(edge));
+            reg_block
+                .slave
+                .modify(|_, w| w.clk_mode_13().bit(clk_mode_13).rsck_data_out().clear_bit());
+        }
+
+        #[cfg(esp32)]
+        fn set_slave_data_mode(&mut self, data_mode: SpiMode) {
+            let reg_block = self.register_block();
+
+            // MISO and MOSI need to be delayed depending on the mode to meet
+            // the timing of the master
+            let (idle_high, edge, miso_delay_mode, mosi_delay_mode, mosi_delay_num) =
+                match data_mode {
+                    SpiMode::Mode0 => (false, false, 0, 2, 2),
+                    SpiMode::Mode1 => (false, true, 2, 0, 0),
+                    SpiMode::Mode2 => (true, true, 0, 1, 2),
+                    SpiMode::Mode3 => (true, false, 1, 0, 0),
+                };
+
+            reg_block.pin.modify(|_, w| w.ck_idle_edge().bit(idle_high));
+            reg_block.user.modify(|_, w| w.ck_i_edge().bit(edge));
+            reg_block.ctrl2.modify(|_, w| unsafe {
+                w.miso_delay_mode()
+                    .bits(miso_delay_mode)
+                    .miso_delay_num()
+                    .bits(0)
+                    .mosi_delay_mode()
+                    .bits(mosi_delay_mode)
+                    .mosi_delay_num()
+                    .bits(mosi_delay_num)
+            });
+        }
+
+        fn write_slave_buffer(&mut self, words: &[u8]) {
+            let fifo_ptr = self.register_block().w0.as_ptr();
+
+            for index in 0..(FIFO_SIZE / 4) {
+                let mut word = [0u8; 4];
+                if let Some(chunk) = words.chunks(4).nth(index) {
+                    word[..chunk.len()].copy_from_slice(chunk);
+                }
+
+                unsafe {
+                    fifo_ptr.add(index).write_volatile(u32::from_le_bytes(word));
+                }
+            }
+        }
+
+        fn read_slave_buffer(&mut self, words: &mut [u8]) {
+            let fifo_ptr = self.register_block().w0.as_ptr();
+
+            for (index, chunk) in words.chunks_mut(4).enumerate() {
+                let reg_val = unsafe { fifo_ptr.add(index).read_volatile() };
+                let len = chunk.len();
+                chunk.copy_from_slice(&reg_val.to_le_bytes()[..len]);
+            }
+        }
+
+        #[cfg(not(esp32))]
+        fn start_slave_transfer(&mut self, words: &[u8]) {
+            let reg_block = self.register_block();
+
+            reg_block.slave.modify(|_, w| w.soft_reset().set_bit());
+            reg_block.slave.modify(|_, w| w.soft_reset().clear_bit());
+
+            self.write_slave_buffer(words);
+
+            reg_block
+                .dma_int_clr
+                .write(|w| w.trans_done_int_clr().set_bit());
+            reg_block
+                .user
+                .modify(|_, w| w.usr_miso().set_bit().usr_mosi().set_bit());
+            reg_block.cmd.modify(|_, w| w.usr().set_bit());
+        }
+
+        #[cfg(esp32)]
+        fn start_slave_transfer(&mut self, words: &[u8]) {
+            let reg_block = self.register_block();
+            let bitlen = (FIFO_SIZE * 8 - 1) as u32;
+
+            self.write_slave_buffer(words);
+
+            reg_block
+                .slv_wrbuf_dlen
+                .write(|w| unsafe { w.slv_wrbuf_dbitlen().bits(bitlen) });
+            reg_block
+                .slv_rdbuf_dlen
+                .write(|w| unsafe { w.slv_rdbuf_dbitlen().bits(bitlen) });
+            reg_block
+                .mosi_dlen
+                .write(|w| unsafe { w.usr_mosi_dbitlen().bits(bitlen) });
+            reg_block
+                .miso_dlen
+                .write(|w| unsafe { w.usr_miso_dbitlen().bits(bitlen) });
+
+            reg_block
+                .user
+                .modify(|_, w| w.usr_miso().set_bit().usr_mosi().set_bit());
+            reg_block.slave.modify(|_, w| w.trans_done().clear_bit());
+            reg_block.cmd.modify(|_, w| w.usr().set_bit());
+        }
+
+        #[cfg(not(esp32))]
+        fn is_slave_transfer_done(&self) -> bool {
+            self.register_block()
+                .dma_int_raw
+                .read()
+                .trans_done_int_raw()
+                .bit_is_set()
+        }
+
+        #[cfg(esp32)]
+        fn is_slave_transfer_done(&self) -> bool {
+            self.register_block().slave.read().trans_done().bit_is_set()
+        }
+
+        #[cfg(not(esp32))]
+        fn slave_received_bytes(&self) -> usize {
+            self.register_block().slave1.read().data_bitlen().bits() as usize / 8
+        }
+
+        #[cfg(esp32)]
+        fn slave_received_bytes(&self) -> usize {
+            self.register_block()
+                .slv_rd_bit
+                .read()
+                .slv_rdata_bit()
+                .bits() as usize
+                / 8
+        }
+    }
+
+    impl<T> InstanceSlave for T where T: Instance {}
+}
+
+pub trait Instance {
+    fn register_block(&self) -> &RegisterBlock;
+
+    fn sclk_signal(&self) -> OutputSignal;
+
+    fn mosi_signal(&self) -> OutputSignal;
+
+    fn miso_signal(&self) -> InputSignal;
+
+    fn cs_signal(&self) -> OutputSignal;
+
+    /// The hardware CS outputs of the peripheral, starting with CS0
+    fn cs_signals(&self) -> &'static [OutputSignal];
+
+    fn sclk_input_signal(&self) -> InputSignal;
+
+    fn cs_input_signal(&self) -> InputSignal;
+
+    fn sio0_input_signal(&self) -> InputSignal;
+
+    fn sio1_output_signal(&self) -> OutputSignal;
+
+    fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl);
+
+    fn init(&mut self) {
+        let reg_block = self.register_block();
+        reg_block.user.modify(|_, w| {
+            w.usr_miso_highpart()
+                .clear_bit()
+                .usr_miso_highpart()
+                .clear_bit()
+                .doutdin()
+                .set_bit()
+                .usr_miso()
+                .set_bit()
+                .usr_mosi()
+                .set_bit()
+                .cs_hold()
+                .set_bit()
+                .usr_dummy_idle()
+                .set_bit()
+                .usr_addr()
+                .clear_bit()
+                .usr_command()
+                .clear_bit()
+        });
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        reg_block.clk_gate.modify(|_, w| {
+            w.clk_en()
+                .set_bit()
+                .mst_clk_active()
+                .set_bit()
+                .mst_clk_sel()
+                .set_bit()
+        });
+
+        reg_block.ctrl.write(|w| unsafe { w.bits(0) });
 
         #[cfg(not(esp32))]
         reg_block.misc.write(|w| unsafe { w.bits(0) });
@@ -526,9 +1960,8 @@ pub trait Instance {
     }
 
     // taken from https://github.com/apache/incubator-nuttx/blob/8267a7618629838231256edfa666e44b5313348e/arch/risc-v/src/esp32c3/esp32c3_spi.c#L496
-    fn setup(&mut self, frequency: HertzU32, clocks: &Clocks) {
+    fn setup(&mut self, frequency: HertzU32, apb_clk_freq: HertzU32) {
         // FIXME: this might not be always true
-        let apb_clk_freq: HertzU32 = HertzU32::Hz(clocks.apb_clock.to_Hz());
 
         let reg_val: u32;
         let duty_cycle = 128;
@@ -570,92 +2003,499 @@ pub trait Instance {
                     pre = 1;
                 }
 
-                if pre > 16 {
-                    pre = 16;
-                }
+                if pre > 16 {
+                    pre = 16;
+                }
+
+                errval = (apb_clk_freq.raw() as i32 / (pre as i32 * n as i32)
+                    - frequency.raw() as i32)
+                    .abs();
+                if bestn == -1 || errval <= besterr {
+                    besterr = errval;
+                    bestn = n as i32;
+                    bestpre = pre as i32;
+                }
+            }
+
+            let n: i32 = bestn;
+            pre = bestpre as i32;
+            let l: i32 = n;
+
+            /* Effectively, this does:
+             *   h = round((duty_cycle * n) / 256)
+             */
+
+            let mut h: i32 = (duty_cycle * n + 127) / 256;
+            if h <= 0 {
+                h = 1;
+            }
+
+            reg_val = (l as u32 - 1)
+                | ((h as u32 - 1) << 6)
+                | ((n as u32 - 1) << 12)
+                | ((pre as u32 - 1) << 18);
+        }
+
+        self.register_block()
+            .clock
+            .write(|w| unsafe { w.bits(reg_val) });
+    }
+
+    #[cfg(not(esp32))]
+    fn set_data_mode(&mut self, data_mode: SpiMode) -> &mut Self {
+        let reg_block = self.register_block();
+
+        match data_mode {
+            SpiMode::Mode0 => {
+                reg_block.misc.modify(|_, w| w.ck_idle_edge().clear_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
+            }
+            SpiMode::Mode1 => {
+                reg_block.misc.modify(|_, w| w.ck_idle_edge().clear_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
+            }
+            SpiMode::Mode2 => {
+                reg_block.misc.modify(|_, w| w.ck_idle_edge().set_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
+            }
+            SpiMode::Mode3 => {
+                reg_block.misc.modify(|_, w| w.ck_idle_edge().set_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
+            }
+        }
+        self
+    }
+
+    #[cfg(esp32)]
+    fn set_data_mode(&mut self, data_mode: SpiMode) -> &mut Self {
+        let reg_block = self.register_block();
+
+        match data_mode {
+            SpiMode::Mode0 => {
+                reg_block.pin.modify(|_, w| w.ck_idle_edge().clear_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
+            }
+            SpiMode::Mode1 => {
+                reg_block.pin.modify(|_, w| w.ck_idle_edge().clear_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
+            }
+            SpiMode::Mode2 => {
+                reg_block.pin.modify(|_, w| w.ck_idle_edge().set_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
+            }
+            SpiMode::Mode3 => {
+                reg_block.pin.modify(|_, w| w.ck_idle_edge().set_bit());
+                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
+            }
+        }
+        self
+    }
+
+    fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+        self.register_block().ctrl.modify(|_, w| {
+            w.rd_bit_order()
+                .bit(read_order == SpiBitOrder::LSBFirst)
+                .wr_bit_order()
+                .bit(write_order == SpiBitOrder::LSBFirst)
+        });
+    }
+
+    #[cfg(not(esp32))]
+    fn select_cs(&mut self, line: ChipSelect) {
+        self.register_block().misc.modify(|_, w| {
+            w.cs0_dis()
+                .bit(line != ChipSelect::Cs0)
+                .cs1_dis()
+                .bit(line != ChipSelect::Cs1)
+                .cs2_dis()
+                .bit(line != ChipSelect::Cs2)
+                .cs3_dis()
+                .bit(line != ChipSelect::Cs3)
+                .cs4_dis()
+                .bit(line != ChipSelect::Cs4)
+                .cs5_dis()
+                .bit(line != ChipSelect::Cs5)
+        });
+    }
+
+    #[cfg(esp32)]
+    fn select_cs(&mut self, line: ChipSelect) {
+        self.register_block().pin.modify(|_, w| {
+            w.cs0_dis()
+                .bit(line != ChipSelect::Cs0)
+                .cs1_dis()
+                .bit(line != ChipSelect::Cs1)
+                .cs2_dis()
+                .bit(line != ChipSelect::Cs2)
+        });
+    }
+
+    /// Delay the start and the end of transfers by the given number of SPI
+    /// clock cycles
+    #[cfg(not(esp32))]
+    fn set_cs_timing(&mut self, setup_cycles: u8, hold_cycles: u8) {
+        let reg_block = self.register_block();
+
+        reg_block
+            .user
+            .modify(|_, w| w.cs_setup().bit(setup_cycles > 0).cs_hold().set_bit());
+        reg_block.user1.modify(|_, w| unsafe {
+            w.cs_setup_time()
+                .bits(u8::min(setup_cycles.saturating_sub(1), 0x1f))
+                .cs_hold_time()
+                .bits(u8::min(hold_cycles.saturating_sub(1), 0x1f))
+        });
+    }
+
+    /// Delay the start and the end of transfers by the given number of SPI
+    /// clock cycles
+    #[cfg(esp32)]
+    fn set_cs_timing(&mut self, setup_cycles: u8, hold_cycles: u8) {
+        let reg_block = self.register_block();
+
+        reg_block
+            .user
+            .modify(|_, w| w.cs_setup().bit(setup_cycles > 0).cs_hold().set_bit());
+        reg_block.ctrl2.modify(|_, w| unsafe {
+            w.setup_time()
+                .bits(u8::min(setup_cycles.saturating_sub(1), 0xf))
+                .hold_time()
+                .bits(u8::min(hold_cycles.saturating_sub(1), 0xf))
+        });
+    }
+
+    /// Write a single word as a frame of `bits` bits
+    fn write_word<W: Word>(&mut self, word: W, bits: u8) -> nb::Result<(), Infallible> {
+        if self.register_block().cmd.read().usr().bit_is_set() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.start_frames(&[word], bits);
+
+        Ok(())
+    }
+
+    /// Read the single word received by the last [`Instance::write_word`]
+    fn read_word<W: Word>(&mut self, bits: u8) -> nb::Result<W, Infallible> {
+        if self.register_block().cmd.read().usr().bit_is_set() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let mut word = [W::from_u32(0)];
+        self.read_frames(&mut word, bits);
+
+        Ok(word[0])
+    }
+
+    /// Write words as frames of `bits` bits
+    ///
+    /// Like [`Instance::write_bytes`] this function doesn't wait for the last
+    /// chunk to be sent.
+    fn write_words<W: Word>(&mut self, words: &[W], bits: u8) -> Result<(), Infallible> {
+        for chunk in words.chunks(FIFO_SIZE * 8 / bits as usize) {
+            self.flush()?;
+            self.start_frames(chunk, bits);
+        }
+
+        Ok(())
+    }
+
+    /// Read words as frames of `bits` bits, sending out zeros
+    fn read_words<W: Word>(&mut self, words: &mut [W], bits: u8) -> Result<(), Infallible> {
+        words.fill(W::from_u32(EMPTY_WRITE_PAD as u32));
+        self.transfer_words(words, bits)
+    }
+
+    /// Transfer words as frames of `bits` bits in place
+    fn transfer_words<W: Word>(&mut self, words: &mut [W], bits: u8) -> Result<(), Infallible> {
+        for chunk in words.chunks_mut(FIFO_SIZE * 8 / bits as usize) {
+            self.flush()?;
+            self.start_frames(chunk, bits);
+            self.flush()?;
+            self.read_frames(chunk, bits);
+        }
+
+        Ok(())
+    }
+
+    /// Packs `frames` into the FIFO and starts the transfer
+    fn start_frames<W: Word>(&mut self, frames: &[W], bits: u8) {
+        let reg_block = self.register_block();
+        let lsb_first = reg_block.ctrl.read().wr_bit_order().bit_is_set();
+        let mask = u64::MAX >> (64 - bits as u32);
+
+        let mut bytes = [0u8; FIFO_SIZE];
+        let mut index = 0;
+        let mut acc = 0u64;
+        let mut acc_bits = 0;
+
+        for frame in frames {
+            let value = frame.into_u32() as u64 & mask;
+            if lsb_first {
+                acc |= value << acc_bits;
+            } else {
+                acc = (acc << bits) | value;
+            }
+            acc_bits += bits as u32;
+
+            while acc_bits >= 8 {
+                acc_bits -= 8;
+                if lsb_first {
+                    bytes[index] = acc as u8;
+                    acc >>= 8;
+                } else {
+                    bytes[index] = (acc >> acc_bits) as u8;
+                }
+                index += 1;
+            }
+        }
+
+        if acc_bits > 0 {
+            bytes[index] = if lsb_first {
+                acc as u8
+            } else {
+                (acc << (8 - acc_bits)) as u8
+            };
+        }
+
+        let fifo_ptr = reg_block.w0.as_ptr();
+        for (index, chunk) in bytes.chunks(4).enumerate() {
+            let word = [chunk[0], chunk[1], chunk[2], chunk[3]];
+            unsafe {
+                fifo_ptr.add(index).write_volatile(u32::from_le_bytes(word));
+            }
+        }
+
+        self.configure_datalen(frames.len() as u32 * bits as u32);
+        self.update();
+
+        reg_block.cmd.modify(|_, w| w.usr().set_bit());
+    }
+
+    /// Unpacks the frames received by the last transfer from the FIFO
+    fn read_frames<W: Word>(&mut self, frames: &mut [W], bits: u8) {
+        let reg_block = self.register_block();
+        let lsb_first = reg_block.ctrl.read().rd_bit_order().bit_is_set();
+        let mask = u64::MAX >> (64 - bits as u32);
+
+        let fifo_ptr = reg_block.w0.as_ptr();
+        let mut bytes = (0..FIFO_SIZE).map(|index| {
+            unsafe { fifo_ptr.add(index / 4).read_volatile() }.to_le_bytes()[index % 4]
+        });
+        let mut acc = 0u64;
+        let mut acc_bits = 0;
+
+        for frame in frames.iter_mut() {
+            while acc_bits < bits as u32 {
+                let byte = bytes.next().unwrap_or_default() as u64;
+                if lsb_first {
+                    acc |= byte << acc_bits;
+                } else {
+                    acc = (acc << 8) | byte;
+                }
+                acc_bits += 8;
+            }
+
+            acc_bits -= bits as u32;
+            if lsb_first {
+                *frame = W::from_u32((acc & mask) as u32);
+                acc >>= bits;
+            } else {
+                *frame = W::from_u32(((acc >> acc_bits) & mask) as u32);
+            }
+        }
+    }
+
+    /// Configures the phases of a half-duplex transfer of `len` data bytes
+    fn setup_half_duplex(
+        &mut self,
+        is_write: bool,
+        len: usize,
+        data_mode: SpiDataMode,
+        cmd: Option<Command>,
+        address: Option<Address>,
+        dummy: u8,
+    ) -> Result<(), Error> {
+        if len > FIFO_SIZE {
+            return Err(Error::FifoSizeExceeded);
+        }
+
+        if matches!(cmd, Some(cmd) if cmd.length == 0 || cmd.length > 16)
+            || matches!(address, Some(address) if address.length == 0 || address.length > 32)
+        {
+            return Err(Error::InvalidPhaseLength);
+        }
+
+        self.set_line_modes(
+            cmd.map_or(SpiDataMode::Single, |cmd| cmd.mode),
+            address.map_or(SpiDataMode::Single, |address| address.mode),
+            data_mode,
+        )?;
+
+        let reg_block = self.register_block();
+
+        // The clock needs to keep running during the dummy phase, as devices
+        // usually count the dummy cycles
+        reg_block.user.modify(|_, w| {
+            w.doutdin()
+                .clear_bit()
+                .usr_command()
+                .bit(cmd.is_some())
+                .usr_addr()
+                .bit(address.is_some())
+                .usr_dummy()
+                .bit(dummy > 0)
+                .usr_dummy_idle()
+                .clear_bit()
+                .usr_mosi()
+                .bit(is_write && len > 0)
+                .usr_miso()
+                .bit(!is_write && len > 0)
+        });
+
+        if let Some(cmd) = cmd {
+            // The command is sent starting with bit 7 of the register value,
+            // followed by bit 15, so the bytes are swapped to send it MSB first
+            let value = (cmd.value << (16 - cmd.length)).swap_bytes();
+            reg_block.user2.modify(|_, w| unsafe {
+                w.usr_command_bitlen()
+                    .bits(cmd.length - 1)
+                    .usr_command_value()
+                    .bits(value)
+            });
+        }
 
-                errval = (apb_clk_freq.raw() as i32 / (pre as i32 * n as i32)
-                    - frequency.raw() as i32)
-                    .abs();
-                if bestn == -1 || errval <= besterr {
-                    besterr = errval;
-                    bestn = n as i32;
-                    bestpre = pre as i32;
-                }
-            }
+        if let Some(address) = address {
+            // The address is sent starting with the MSB of the register
+            reg_block
+                .user1
+                .modify(|_, w| unsafe { w.usr_addr_bitlen().bits(address.length - 1) });
+            reg_block
+                .addr
+                .write(|w| unsafe { w.bits(address.value << (32 - address.length)) });
+        }
 
-            let n: i32 = bestn;
-            pre = bestpre as i32;
-            let l: i32 = n;
+        if dummy > 0 {
+            reg_block
+                .user1
+                .modify(|_, w| unsafe { w.usr_dummy_cyclelen().bits(dummy - 1) });
+        }
 
-            /* Effectively, this does:
-             *   h = round((duty_cycle * n) / 256)
-             */
+        if len > 0 {
+            self.configure_datalen(len as u32 * 8);
+        }
 
-            let mut h: i32 = (duty_cycle * n + 127) / 256;
-            if h <= 0 {
-                h = 1;
-            }
+        Ok(())
+    }
 
-            reg_val = (l as u32 - 1)
-                | ((h as u32 - 1) << 6)
-                | ((n as u32 - 1) << 12)
-                | ((pre as u32 - 1) << 18);
-        }
+    /// Restores the configuration for full-duplex transfers
+    fn end_half_duplex(&mut self) {
+        self.register_block().user.modify(|_, w| {
+            w.doutdin()
+                .set_bit()
+                .usr_command()
+                .clear_bit()
+                .usr_addr()
+                .clear_bit()
+                .usr_dummy()
+                .clear_bit()
+                .usr_dummy_idle()
+                .set_bit()
+                .usr_mosi()
+                .set_bit()
+                .usr_miso()
+                .set_bit()
+        });
 
-        self.register_block()
-            .clock
-            .write(|w| unsafe { w.bits(reg_val) });
+        self.set_line_modes(
+            SpiDataMode::Single,
+            SpiDataMode::Single,
+            SpiDataMode::Single,
+        )
+        .ok();
     }
 
     #[cfg(not(esp32))]
-    fn set_data_mode(&mut self, data_mode: SpiMode) -> &mut Self {
+    fn set_line_modes(
+        &mut self,
+        cmd: SpiDataMode,
+        address: SpiDataMode,
+        data: SpiDataMode,
+    ) -> Result<(), Error> {
         let reg_block = self.register_block();
 
-        match data_mode {
-            SpiMode::Mode0 => {
-                reg_block.misc.modify(|_, w| w.ck_idle_edge().clear_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
-            }
-            SpiMode::Mode1 => {
-                reg_block.misc.modify(|_, w| w.ck_idle_edge().clear_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
-            }
-            SpiMode::Mode2 => {
-                reg_block.misc.modify(|_, w| w.ck_idle_edge().set_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
-            }
-            SpiMode::Mode3 => {
-                reg_block.misc.modify(|_, w| w.ck_idle_edge().set_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
-            }
-        }
-        self
+        reg_block.ctrl.modify(|_, w| {
+            w.fcmd_dual()
+                .bit(cmd == SpiDataMode::Dual)
+                .fcmd_quad()
+                .bit(cmd == SpiDataMode::Quad)
+                .faddr_dual()
+                .bit(address == SpiDataMode::Dual)
+                .faddr_quad()
+                .bit(address == SpiDataMode::Quad)
+                .fread_dual()
+                .bit(data == SpiDataMode::Dual)
+                .fread_quad()
+                .bit(data == SpiDataMode::Quad)
+        });
+        reg_block.user.modify(|_, w| {
+            w.fwrite_dual()
+                .bit(data == SpiDataMode::Dual)
+                .fwrite_quad()
+                .bit(data == SpiDataMode::Quad)
+        });
+
+        Ok(())
     }
 
     #[cfg(esp32)]
-    fn set_data_mode(&mut self, data_mode: SpiMode) -> &mut Self {
+    fn set_line_modes(
+        &mut self,
+        cmd: SpiDataMode,
+        address: SpiDataMode,
+        data: SpiDataMode,
+    ) -> Result<(), Error> {
+        // The ESP32 always sends the command on a single line, the address
+        // either on a single line or on as many lines as the data (DIO/QIO)
+        if cmd != SpiDataMode::Single {
+            return Err(Error::Unsupported);
+        }
+
+        let (dual, quad, dio, qio) = match (address, data) {
+            (SpiDataMode::Single, SpiDataMode::Single) => (false, false, false, false),
+            (SpiDataMode::Single, SpiDataMode::Dual) => (true, false, false, false),
+            (SpiDataMode::Single, SpiDataMode::Quad) => (false, true, false, false),
+            (SpiDataMode::Dual, SpiDataMode::Dual) => (false, false, true, false),
+            (SpiDataMode::Quad, SpiDataMode::Quad) => (false, false, false, true),
+            _ => return Err(Error::Unsupported),
+        };
+
         let reg_block = self.register_block();
 
-        match data_mode {
-            SpiMode::Mode0 => {
-                reg_block.pin.modify(|_, w| w.ck_idle_edge().clear_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
-            }
-            SpiMode::Mode1 => {
-                reg_block.pin.modify(|_, w| w.ck_idle_edge().clear_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
-            }
-            SpiMode::Mode2 => {
-                reg_block.pin.modify(|_, w| w.ck_idle_edge().set_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().set_bit());
-            }
-            SpiMode::Mode3 => {
-                reg_block.pin.modify(|_, w| w.ck_idle_edge().set_bit());
-                reg_block.user.modify(|_, w| w.ck_out_edge().clear_bit());
-            }
-        }
-        self
+        reg_block.ctrl.modify(|_, w| {
+            w.fastrd_mode()
+                .bit(dual || quad || dio || qio)
+                .fread_dual()
+                .bit(dual)
+                .fread_quad()
+                .bit(quad)
+                .fread_dio()
+                .bit(dio)
+                .fread_qio()
+                .bit(qio)
+        });
+        reg_block.user.modify(|_, w| {
+            w.fwrite_dual()
+                .bit(dual)
+                .fwrite_quad()
+                .bit(quad)
+                .fwrite_dio()
+                .bit(dio)
+                .fwrite_qio()
+                .bit(qio)
+        });
+
+        Ok(())
     }
 
     fn read_byte(&mut self) -> nb::Result<u8, Infallible> {
@@ -841,6 +2681,17 @@ pub trait Instance {
     }
 }
 
+/// An SPI peripheral with four data lines
+pub trait QuadInstance: Instance {
+    fn sio2_output_signal(&self) -> OutputSignal;
+
+    fn sio2_input_signal(&self) -> InputSignal;
+
+    fn sio3_output_signal(&self) -> OutputSignal;
+
+    fn sio3_input_signal(&self) -> InputSignal;
+}
+
 #[cfg(any(esp32c3))]
 impl Instance for crate::pac::SPI2 {
     #[inline(always)]
@@ -868,12 +2719,67 @@ impl Instance for crate::pac::SPI2 {
         OutputSignal::FSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::FSPICS0,
+            OutputSignal::FSPICS1,
+            OutputSignal::FSPICS2,
+            OutputSignal::FSPICS3,
+            OutputSignal::FSPICS4,
+            OutputSignal::FSPICS5,
+        ]
+    }
+
+    #[inline(always)]
+    fn sclk_input_signal(&self) -> InputSignal {
+        InputSignal::FSPICLK
+    }
+
+    #[inline(always)]
+    fn cs_input_signal(&self) -> InputSignal {
+        InputSignal::FSPICS0
+    }
+
+    #[inline(always)]
+    fn sio0_input_signal(&self) -> InputSignal {
+        InputSignal::FSPID
+    }
+
+    #[inline(always)]
+    fn sio1_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIQ
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl) {
         peripheral_clock_control.enable(crate::system::Peripheral::Spi2);
     }
 }
 
+#[cfg(any(esp32c3))]
+impl QuadInstance for crate::pac::SPI2 {
+    #[inline(always)]
+    fn sio2_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIWP
+    }
+
+    #[inline(always)]
+    fn sio2_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIWP
+    }
+
+    #[inline(always)]
+    fn sio3_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIHD
+    }
+
+    #[inline(always)]
+    fn sio3_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIHD
+    }
+}
+
 #[cfg(any(esp32))]
 impl Instance for crate::pac::SPI2 {
     #[inline(always)]
@@ -901,12 +2807,64 @@ impl Instance for crate::pac::SPI2 {
         OutputSignal::HSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::HSPICS0,
+            OutputSignal::HSPICS1,
+            OutputSignal::HSPICS2,
+        ]
+    }
+
+    #[inline(always)]
+    fn sclk_input_signal(&self) -> InputSignal {
+        InputSignal::HSPICLK
+    }
+
+    #[inline(always)]
+    fn cs_input_signal(&self) -> InputSignal {
+        InputSignal::HSPICS0
+    }
+
+    #[inline(always)]
+    fn sio0_input_signal(&self) -> InputSignal {
+        InputSignal::HSPID
+    }
+
+    #[inline(always)]
+    fn sio1_output_signal(&self) -> OutputSignal {
+        OutputSignal::HSPIQ
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl) {
         peripheral_clock_control.enable(crate::system::Peripheral::Spi2);
     }
 }
 
+#[cfg(any(esp32))]
+impl QuadInstance for crate::pac::SPI2 {
+    #[inline(always)]
+    fn sio2_output_signal(&self) -> OutputSignal {
+        OutputSignal::HSPIWP
+    }
+
+    #[inline(always)]
+    fn sio2_input_signal(&self) -> InputSignal {
+        InputSignal::HSPIWP
+    }
+
+    #[inline(always)]
+    fn sio3_output_signal(&self) -> OutputSignal {
+        OutputSignal::HSPIHD
+    }
+
+    #[inline(always)]
+    fn sio3_input_signal(&self) -> InputSignal {
+        InputSignal::HSPIHD
+    }
+}
+
 #[cfg(any(esp32))]
 impl Instance for crate::pac::SPI3 {
     #[inline(always)]
@@ -934,12 +2892,64 @@ impl Instance for crate::pac::SPI3 {
         OutputSignal::VSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::VSPICS0,
+            OutputSignal::VSPICS1,
+            OutputSignal::VSPICS2,
+        ]
+    }
+
+    #[inline(always)]
+    fn sclk_input_signal(&self) -> InputSignal {
+        InputSignal::VSPICLK
+    }
+
+    #[inline(always)]
+    fn cs_input_signal(&self) -> InputSignal {
+        InputSignal::VSPICS0
+    }
+
+    #[inline(always)]
+    fn sio0_input_signal(&self) -> InputSignal {
+        InputSignal::VSPID
+    }
+
+    #[inline(always)]
+    fn sio1_output_signal(&self) -> OutputSignal {
+        OutputSignal::VSPIQ
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl) {
         peripheral_clock_control.enable(crate::system::Peripheral::Spi3)
     }
 }
 
+#[cfg(any(esp32))]
+impl QuadInstance for crate::pac::SPI3 {
+    #[inline(always)]
+    fn sio2_output_signal(&self) -> OutputSignal {
+        OutputSignal::VSPIWP
+    }
+
+    #[inline(always)]
+    fn sio2_input_signal(&self) -> InputSignal {
+        InputSignal::VSPIWP
+    }
+
+    #[inline(always)]
+    fn sio3_output_signal(&self) -> OutputSignal {
+        OutputSignal::VSPIHD
+    }
+
+    #[inline(always)]
+    fn sio3_input_signal(&self) -> InputSignal {
+        InputSignal::VSPIHD
+    }
+}
+
 #[cfg(any(esp32s2, esp32s3))]
 impl Instance for crate::pac::SPI2 {
     #[inline(always)]
@@ -967,12 +2977,67 @@ impl Instance for crate::pac::SPI2 {
         OutputSignal::FSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::FSPICS0,
+            OutputSignal::FSPICS1,
+            OutputSignal::FSPICS2,
+            OutputSignal::FSPICS3,
+            OutputSignal::FSPICS4,
+            OutputSignal::FSPICS5,
+        ]
+    }
+
+    #[inline(always)]
+    fn sclk_input_signal(&self) -> InputSignal {
+        InputSignal::FSPICLK
+    }
+
+    #[inline(always)]
+    fn cs_input_signal(&self) -> InputSignal {
+        InputSignal::FSPICS0
+    }
+
+    #[inline(always)]
+    fn sio0_input_signal(&self) -> InputSignal {
+        InputSignal::FSPID
+    }
+
+    #[inline(always)]
+    fn sio1_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIQ
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl) {
         peripheral_clock_control.enable(crate::system::Peripheral::Spi2)
     }
 }
 
+#[cfg(any(esp32s2, esp32s3))]
+impl QuadInstance for crate::pac::SPI2 {
+    #[inline(always)]
+    fn sio2_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIWP
+    }
+
+    #[inline(always)]
+    fn sio2_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIWP
+    }
+
+    #[inline(always)]
+    fn sio3_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIHD
+    }
+
+    #[inline(always)]
+    fn sio3_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIHD
+    }
+}
+
 #[cfg(any(esp32s2, esp32s3))]
 impl Instance for crate::pac::SPI3 {
     #[inline(always)]
@@ -1000,8 +3065,60 @@ impl Instance for crate::pac::SPI3 {
         OutputSignal::SPI3_CS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::SPI3_CS0,
+            OutputSignal::SPI3_CS1,
+            OutputSignal::SPI3_CS2,
+        ]
+    }
+
+    #[inline(always)]
+    fn sclk_input_signal(&self) -> InputSignal {
+        InputSignal::SPI3_CLK
+    }
+
+    #[inline(always)]
+    fn cs_input_signal(&self) -> InputSignal {
+        InputSignal::SPI3_CS0
+    }
+
+    #[inline(always)]
+    fn sio0_input_signal(&self) -> InputSignal {
+        InputSignal::SPI3_D
+    }
+
+    #[inline(always)]
+    fn sio1_output_signal(&self) -> OutputSignal {
+        OutputSignal::SPI3_Q
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self, peripheral_clock_control: &mut PeripheralClockControl) {
         peripheral_clock_control.enable(crate::system::Peripheral::Spi3)
     }
 }
+
+#[cfg(esp32s3)]
+impl QuadInstance for crate::pac::SPI3 {
+    #[inline(always)]
+    fn sio2_output_signal(&self) -> OutputSignal {
+        OutputSignal::SPI3_WP
+    }
+
+    #[inline(always)]
+    fn sio2_input_signal(&self) -> InputSignal {
+        InputSignal::SPI3_WP
+    }
+
+    #[inline(always)]
+    fn sio3_output_signal(&self) -> OutputSignal {
+        OutputSignal::SPI3_HD
+    }
+
+    #[inline(always)]
+    fn sio3_input_signal(&self) -> InputSignal {
+        InputSignal::SPI3_HD
+    }
+}
@@ -24,6 +24,10 @@ pub enum Peripheral {
     Ledc,
     #[cfg(esp32c3)]
     ApbSarAdc,
+    #[cfg(gdma)]
+    Gdma,
+    #[cfg(pdma)]
+    Dma,
 }
 
 /// Controls the enablement of peripheral clocks.
@@ -41,6 +45,9 @@ impl PeripheralClockControl {
         #[cfg(esp32)]
         let (perip_clk_en0, perip_rst_en0) = { (&system.perip_clk_en, &system.perip_rst_en) };
 
+        #[cfg(gdma)]
+        let (perip_clk_en1, perip_rst_en1) = { (&system.perip_clk_en1, &system.perip_rst_en1) };
+
         match peripheral {
             Peripheral::Spi2 => {
                 perip_clk_en0.modify(|_, w| w.spi2_clk_en().set_bit());
@@ -78,6 +85,23 @@ impl PeripheralClockControl {
                 perip_clk_en0.modify(|_, w| w.apb_saradc_clk_en().set_bit());
                 perip_rst_en0.modify(|_, w| w.apb_saradc_rst().clear_bit());
             }
+            #[cfg(gdma)]
+            Peripheral::Gdma => {
+                perip_clk_en1.modify(|_, w| w.dma_clk_en().set_bit());
+                perip_rst_en1.modify(|_, w| w.dma_rst().clear_bit());
+            }
+            #[cfg(esp32)]
+            Peripheral::Dma => {
+                perip_clk_en0.modify(|_, w| w.spi_dma_clk_en().set_bit());
+                perip_rst_en0.modify(|_, w| w.spi_dma_rst().clear_bit());
+            }
+            #[cfg(esp32s2)]
+            Peripheral::Dma => {
+                perip_clk_en0
+                    .modify(|_, w| w.spi2_dma_clk_en().set_bit().spi3_dma_clk_en().set_bit());
+                perip_rst_en0
+                    .modify(|_, w| w.spi2_dma_rst().clear_bit().spi3_dma_rst().clear_bit());
+            }
         }
     }
 }
@@ -92,12 +116,21 @@ pub struct CpuControl {
     _private: (),
 }
 
+/// The DMA functionality of the peripherals (on chips without a dedicated DMA
+/// controller)
+#[cfg(pdma)]
+pub struct Dma {
+    _private: (),
+}
+
 /// The SYSTEM/DPORT splitted into it's different logical parts.
 pub struct SystemParts {
     _private: (),
     pub peripheral_clock_control: PeripheralClockControl,
     pub clock_control: SystemClockControl,
     pub cpu_control: CpuControl,
+    #[cfg(pdma)]
+    pub dma: Dma,
 }
 
 /// Extension trait to split a SYSTEM/DPORT peripheral in independent logical
@@ -118,6 +151,8 @@ impl SystemExt for SystemPeripheral {
             peripheral_clock_control: PeripheralClockControl { _private: () },
             clock_control: SystemClockControl { _private: () },
             cpu_control: CpuControl { _private: () },
+            #[cfg(pdma)]
+            dma: Dma { _private: () },
         }
     }
 }
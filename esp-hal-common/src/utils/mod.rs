@@ -1,5 +1,8 @@
 //! Helper Utils
 
+pub mod protocols;
+pub mod ring_buffer;
+
 // Only provide adapter when feature is enabled!
 #[cfg(feature = "smartled")]
 pub mod smart_leds_adapter;
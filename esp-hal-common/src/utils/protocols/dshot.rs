@@ -0,0 +1,26 @@
+//! # DShot ESC Protocol
+//!
+//! See [`rmt_protocols::dshot`] for the format of the frames. Frames of
+//! bidirectional DShot ([`DshotFrame::inverted`]) start at the low level.
+
+pub use rmt_protocols::dshot::{DshotFrame, DshotSpeed, FRAME_LENGTH, MAX_THROTTLE};
+
+use super::{pulse, timing, ProtocolError};
+use crate::pulse_control::PulseCode;
+
+/// Encode a DShot frame
+pub fn encode(frame: DshotFrame, speed: DshotSpeed) -> [PulseCode; FRAME_LENGTH] {
+    rmt_protocols::dshot::encode(frame, speed).map(|timing| pulse(!frame.inverted, timing))
+}
+
+/// Decode a received DShot frame
+///
+/// A bit is taken as a one if its active level lasts longer than half of
+/// the bit period.
+pub fn decode(
+    codes: &[PulseCode],
+    speed: DshotSpeed,
+    inverted: bool,
+) -> Result<DshotFrame, ProtocolError> {
+    rmt_protocols::dshot::decode(codes.iter().map(timing), speed, inverted)
+}
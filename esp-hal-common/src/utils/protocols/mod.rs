@@ -0,0 +1,48 @@
+//! # RMT Protocol Encoders and Decoders
+//!
+//! Encoders turn protocol frames into [`PulseCode`] sequences that can be
+//! sent with an RMT output channel, decoders turn the sequences received by
+//! an RMT input channel back into frames.
+//!
+//! * [`nec`]: NEC infrared remote protocol
+//! * [`rc5`]: Philips RC5 infrared remote protocol
+//! * [`dshot`]: DShot150/300/600 ESC protocol
+//!
+//! The frame, checksum and bit timing logic lives in the hardware independent
+//! `esp-hal-rmt-protocols` crate (and is tested there), the modules in here
+//! only convert its pulse lengths from and to [`PulseCode`]s.
+//!
+//! The infrared encoders only produce the envelope of the signal, the
+//! carrier (usually 38 kHz or 36 kHz) has to be configured on the channel
+//! with `OutputChannel::set_carrier`. The decoders only look at the lengths
+//! of the received pulses, so they work with inverting IR receiver modules as
+//! well.
+
+use fugit::NanosDurationU32;
+pub use rmt_protocols::ProtocolError;
+use rmt_protocols::Timing;
+
+use crate::pulse_control::PulseCode;
+
+pub mod dshot;
+pub mod nec;
+pub mod rc5;
+
+// Create a pulse code which starts at the `active` level, a timing of zero
+// stays an end marker
+fn pulse(active: bool, timing: Timing) -> PulseCode {
+    match timing {
+        (0, 0) => PulseCode::end_marker(),
+        (mark, space) => PulseCode {
+            level1: active,
+            length1: NanosDurationU32::from_ticks(mark),
+            level2: !active,
+            length2: NanosDurationU32::from_ticks(space),
+        },
+    }
+}
+
+// The lengths of the two parts of a received pulse code
+fn timing(code: &PulseCode) -> Timing {
+    (code.length1.ticks(), code.length2.ticks())
+}
@@ -0,0 +1,28 @@
+//! # NEC Infrared Remote Protocol
+//!
+//! See [`rmt_protocols::nec`] for the format of the frames.
+
+pub use rmt_protocols::nec::{NecCommand, FRAME_LENGTH, REPEAT_LENGTH};
+
+use super::{pulse, timing, ProtocolError};
+use crate::pulse_control::PulseCode;
+
+/// Encode a standard NEC frame
+pub fn encode(address: u8, command: u8) -> [PulseCode; FRAME_LENGTH] {
+    rmt_protocols::nec::encode(address, command).map(|timing| pulse(true, timing))
+}
+
+/// Encode an extended NEC frame with a 16 bit address
+pub fn encode_extended(address: u16, command: u8) -> [PulseCode; FRAME_LENGTH] {
+    rmt_protocols::nec::encode_extended(address, command).map(|timing| pulse(true, timing))
+}
+
+/// Encode a repeat code
+pub fn encode_repeat() -> [PulseCode; REPEAT_LENGTH] {
+    rmt_protocols::nec::encode_repeat().map(|timing| pulse(true, timing))
+}
+
+/// Decode a received NEC frame or repeat code
+pub fn decode(codes: &[PulseCode]) -> Result<NecCommand, ProtocolError> {
+    rmt_protocols::nec::decode(codes.iter().map(timing))
+}
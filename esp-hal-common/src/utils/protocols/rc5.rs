@@ -0,0 +1,18 @@
+//! # Philips RC5 Infrared Remote Protocol
+//!
+//! See [`rmt_protocols::rc5`] for the format of the frames.
+
+pub use rmt_protocols::rc5::{Rc5Command, FRAME_LENGTH};
+
+use super::{pulse, timing, ProtocolError};
+use crate::pulse_control::PulseCode;
+
+/// Encode an RC5 frame
+pub fn encode(frame: Rc5Command) -> Result<[PulseCode; FRAME_LENGTH], ProtocolError> {
+    Ok(rmt_protocols::rc5::encode(frame)?.map(|timing| pulse(true, timing)))
+}
+
+/// Decode a received RC5 frame
+pub fn decode(codes: &[PulseCode]) -> Result<Rc5Command, ProtocolError> {
+    rmt_protocols::rc5::decode(codes.iter().map(timing))
+}
@@ -0,0 +1,239 @@
+//! Lock-free single producer single consumer ring buffer
+//!
+//! The buffer is split into a [`Producer`] and a [`Consumer`], which can be
+//! used concurrently, e.g. one of them from an interrupt handler. Only atomic
+//! loads and stores are used, so this also works on chips without atomic
+//! read-modify-write instructions.
+//!
+//! ```ignore
+//! static mut BUFFER: RingBuffer<[u8; 64]> = RingBuffer::new();
+//!
+//! let buffer: &'static mut RingBuffer = unsafe { &mut BUFFER };
+//! let (mut producer, mut consumer) = buffer.split();
+//! producer.push(42);
+//! assert_eq!(consumer.pop(), Some(42));
+//! ```
+
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Ring buffer holding up to one byte less than the size of its storage
+///
+/// The storage is an array when the buffer is created and is referred to as
+/// `RingBuffer` (a slice) everywhere else, so that buffers of different sizes
+/// have the same type.
+pub struct RingBuffer<B: ?Sized = [u8]> {
+    // Index of the next byte to read, only written by the consumer
+    head: AtomicUsize,
+    // Index of the next byte to write, only written by the producer
+    tail: AtomicUsize,
+    // Size of the storage, which can't be read from `buffer` without
+    // borrowing it
+    size: usize,
+    buffer: UnsafeCell<B>,
+}
+
+// The producer and the consumer only access the bytes between `tail` and
+// `head` and between `head` and `tail` respectively
+unsafe impl<B: ?Sized + Send> Sync for RingBuffer<B> {}
+
+impl<const N: usize> RingBuffer<[u8; N]> {
+    /// Create an empty ring buffer
+    pub const fn new() -> Self {
+        RingBuffer {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            size: N,
+            buffer: UnsafeCell::new([0; N]),
+        }
+    }
+}
+
+impl<const N: usize> Default for RingBuffer<[u8; N]> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RingBuffer {
+    /// Split into the producer and the consumer, dropping any data left in
+    /// the buffer
+    pub fn split(&mut self) -> (Producer<'_>, Consumer<'_>) {
+        *self.head.get_mut() = 0;
+        *self.tail.get_mut() = 0;
+
+        let ring: &Self = self;
+        (Producer { ring }, Consumer { ring })
+    }
+
+    /// Maximum number of bytes the buffer can hold
+    pub fn capacity(&self) -> usize {
+        self.size.saturating_sub(1)
+    }
+
+    /// Number of bytes in the buffer
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if tail >= head {
+            tail - head
+        } else {
+            self.size - head + tail
+        }
+    }
+
+    /// Whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire) == self.tail.load(Ordering::Acquire)
+    }
+
+    /// Whether the buffer is full
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    fn next(&self, index: usize) -> usize {
+        if index + 1 == self.size {
+            0
+        } else {
+            index + 1
+        }
+    }
+}
+
+/// Writing end of a [`RingBuffer`]
+pub struct Producer<'a> {
+    ring: &'a RingBuffer,
+}
+
+impl<'a> Producer<'a> {
+    /// Append a byte, returns `false` if the buffer is full
+    pub fn push(&mut self, byte: u8) -> bool {
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+        let next = self.ring.next(tail);
+
+        if self.ring.size == 0 || next == self.ring.head.load(Ordering::Acquire) {
+            return false;
+        }
+
+        // The consumer doesn't access the byte at `tail` until it's published
+        unsafe { (self.ring.buffer.get() as *mut u8).add(tail).write(byte) };
+        self.ring.tail.store(next, Ordering::Release);
+
+        true
+    }
+
+    /// Whether the buffer is full
+    pub fn is_full(&self) -> bool {
+        self.ring.is_full()
+    }
+}
+
+/// Reading end of a [`RingBuffer`]
+pub struct Consumer<'a> {
+    ring: &'a RingBuffer,
+}
+
+impl<'a> Consumer<'a> {
+    /// Remove the oldest byte, returns `None` if the buffer is empty
+    pub fn pop(&mut self) -> Option<u8> {
+        let head = self.ring.head.load(Ordering::Relaxed);
+
+        if head == self.ring.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        // The producer doesn't overwrite the byte at `head` until it's released
+        let byte = unsafe { (self.ring.buffer.get() as *const u8).add(head).read() };
+        self.ring.head.store(self.ring.next(head), Ordering::Release);
+
+        Some(byte)
+    }
+
+    /// Whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let mut storage = RingBuffer::<[u8; 4]>::new();
+        let ring: &mut RingBuffer = &mut storage;
+        let (_, mut consumer) = ring.split();
+
+        assert!(consumer.is_empty());
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn full() {
+        let mut storage = RingBuffer::<[u8; 4]>::new();
+        let ring: &mut RingBuffer = &mut storage;
+        assert_eq!(ring.capacity(), 3);
+
+        let (mut producer, mut consumer) = ring.split();
+        assert!(producer.push(1));
+        assert!(producer.push(2));
+        assert!(producer.push(3));
+        assert!(producer.is_full());
+        assert!(!producer.push(4));
+
+        assert_eq!(consumer.pop(), Some(1));
+        assert!(!producer.is_full());
+        assert!(producer.push(4));
+    }
+
+    #[test]
+    fn wrap() {
+        let mut storage = RingBuffer::<[u8; 4]>::new();
+        let ring: &mut RingBuffer = &mut storage;
+        let (mut producer, mut consumer) = ring.split();
+
+        for byte in 0..10 {
+            assert!(producer.push(byte));
+            assert!(producer.push(byte + 100));
+            assert_eq!(consumer.pop(), Some(byte));
+            assert_eq!(consumer.pop(), Some(byte + 100));
+            assert!(consumer.is_empty());
+        }
+    }
+
+    #[test]
+    fn len_across_wrap() {
+        let mut storage = RingBuffer::<[u8; 4]>::new();
+        let ring: &mut RingBuffer = &mut storage;
+
+        {
+            let (mut producer, mut consumer) = ring.split();
+            producer.push(1);
+            producer.push(2);
+            consumer.pop();
+            consumer.pop();
+            producer.push(3);
+            producer.push(4);
+            producer.push(5);
+        }
+        assert_eq!(ring.len(), 3);
+        assert!(ring.is_full());
+    }
+
+    #[test]
+    fn split_drops_data() {
+        let mut storage = RingBuffer::<[u8; 4]>::new();
+        let ring: &mut RingBuffer = &mut storage;
+
+        ring.split().0.push(1);
+        assert!(!ring.is_empty());
+
+        let (_, mut consumer) = ring.split();
+        assert_eq!(consumer.pop(), None);
+    }
+}
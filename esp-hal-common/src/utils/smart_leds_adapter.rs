@@ -13,7 +13,7 @@
 
 use core::{marker::PhantomData, slice::IterMut};
 
-use fugit::NanosDuration;
+use fugit::NanosDurationU32;
 use smart_leds_trait::{SmartLedsWrite, RGB8};
 
 #[cfg(any(esp32, esp32s2))]
@@ -23,34 +23,24 @@ use crate::{
     pulse_control::{ConfiguredChannel, OutputChannel, PulseCode, RepeatMode, TransmissionError},
 };
 
-// Specifies what clock frequency we're using for the RMT peripheral (if
-// properly configured)
-//
-// TODO: Factor in clock configuration, this needs to be revisited once #24 and
-// #44 have been addressed.
-#[cfg(esp32c3)]
-const SOURCE_CLK_FREQ: u32 = 40_000_000;
-#[cfg(esp32s2)]
-const SOURCE_CLK_FREQ: u32 = 40_000_000;
-#[cfg(esp32)]
-const SOURCE_CLK_FREQ: u32 = 40_000_000;
-#[cfg(esp32s3)]
-const SOURCE_CLK_FREQ: u32 = 40_000_000;
-
 const SK68XX_CODE_PERIOD: u32 = 1200;
 const SK68XX_T0H_NS: u32 = 320;
 const SK68XX_T0L_NS: u32 = SK68XX_CODE_PERIOD - SK68XX_T0H_NS;
 const SK68XX_T1H_NS: u32 = 640;
 const SK68XX_T1L_NS: u32 = SK68XX_CODE_PERIOD - SK68XX_T1H_NS;
 
-const SK68XX_T0H_CYCLES: NanosDuration<u32> =
-    NanosDuration::<u32>::from_ticks((SK68XX_T0H_NS * (SOURCE_CLK_FREQ / 1_000_000)) / 500);
-const SK68XX_T0L_CYCLES: NanosDuration<u32> =
-    NanosDuration::<u32>::from_ticks((SK68XX_T0L_NS * (SOURCE_CLK_FREQ / 1_000_000)) / 500);
-const SK68XX_T1H_CYCLES: NanosDuration<u32> =
-    NanosDuration::<u32>::from_ticks((SK68XX_T1H_NS * (SOURCE_CLK_FREQ / 1_000_000)) / 500);
-const SK68XX_T1L_CYCLES: NanosDuration<u32> =
-    NanosDuration::<u32>::from_ticks((SK68XX_T1L_NS * (SOURCE_CLK_FREQ / 1_000_000)) / 500);
+const SK68XX_ZERO: PulseCode = PulseCode {
+    level1: true,
+    length1: NanosDurationU32::from_ticks(SK68XX_T0H_NS),
+    level2: false,
+    length2: NanosDurationU32::from_ticks(SK68XX_T0L_NS),
+};
+const SK68XX_ONE: PulseCode = PulseCode {
+    level1: true,
+    length1: NanosDurationU32::from_ticks(SK68XX_T1H_NS),
+    level2: false,
+    length2: NanosDurationU32::from_ticks(SK68XX_T1L_NS),
+};
 
 /// All types of errors that can happen during the conversion and transmission
 /// of LED commands
@@ -125,42 +115,33 @@ where
 
     fn convert_rgb_to_pulse(
         value: RGB8,
+        codes: (u32, u32),
         mut_iter: &mut IterMut<u32>,
     ) -> Result<(), LedAdapterError> {
         SmartLedsAdapter::<CHANNEL, PIN, BUFFER_SIZE>::convert_rgb_channel_to_pulses(
-            value.g, mut_iter,
+            value.g, codes, mut_iter,
         )?;
         SmartLedsAdapter::<CHANNEL, PIN, BUFFER_SIZE>::convert_rgb_channel_to_pulses(
-            value.r, mut_iter,
+            value.r, codes, mut_iter,
         )?;
         SmartLedsAdapter::<CHANNEL, PIN, BUFFER_SIZE>::convert_rgb_channel_to_pulses(
-            value.b, mut_iter,
+            value.b, codes, mut_iter,
         )?;
 
         Ok(())
     }
 
+    // `codes` holds the raw pulse codes of a zero and a one bit
     fn convert_rgb_channel_to_pulses(
         channel_value: u8,
+        (zero, one): (u32, u32),
         mut_iter: &mut IterMut<u32>,
     ) -> Result<(), LedAdapterError> {
         for position in [128, 64, 32, 16, 8, 4, 2, 1] {
             *mut_iter.next().ok_or(LedAdapterError::BufferSizeExceeded)? =
                 match channel_value & position {
-                    0 => PulseCode {
-                        level1: true,
-                        length1: SK68XX_T0H_CYCLES,
-                        level2: false,
-                        length2: SK68XX_T0L_CYCLES,
-                    }
-                    .into(),
-                    _ => PulseCode {
-                        level1: true,
-                        length1: SK68XX_T1H_CYCLES,
-                        level2: false,
-                        length2: SK68XX_T1L_CYCLES,
-                    }
-                    .into(),
+                    0 => zero,
+                    _ => one,
                 }
         }
 
@@ -185,6 +166,17 @@ where
         T: Iterator<Item = I>,
         I: Into<Self::Color>,
     {
+        // Convert the bit timings into ticks of the channel clock once
+        let tick_frequency = self.channel.tick_frequency();
+        let codes = (
+            SK68XX_ZERO
+                .to_raw(tick_frequency)
+                .map_err(LedAdapterError::TransmissionError)?,
+            SK68XX_ONE
+                .to_raw(tick_frequency)
+                .map_err(LedAdapterError::TransmissionError)?,
+        );
+
         // We always start from the beginning of the buffer
         let mut seq_iter = self.rmt_buffer.iter_mut();
 
@@ -194,6 +186,7 @@ where
         for item in iterator {
             SmartLedsAdapter::<CHANNEL, PIN, BUFFER_SIZE>::convert_rgb_to_pulse(
                 item.into(),
+                codes,
                 &mut seq_iter,
             )?;
         }
@@ -0,0 +1,235 @@
+//! # DShot ESC Protocol
+//!
+//! A DShot frame consists of 16 bits sent MSB first: an 11 bit throttle
+//! value, a telemetry request bit and a 4 bit CRC. Throttle values 1 to 47
+//! are special commands, 0 disarms the motor. Each bit starts with a high
+//! level, a one is high for 75% of the bit period and a zero for 37.5%.
+//!
+//! Bidirectional DShot inverts the CRC (and the signal levels), this is
+//! selected with [`DshotFrame::inverted`]. The RMT driver then starts the
+//! pulses at the low level.
+
+use crate::{ProtocolError, Timing};
+
+/// Number of pulse codes of a frame (16 bits and the end marker)
+pub const FRAME_LENGTH: usize = 17;
+
+const BITS: usize = 16;
+
+/// Highest possible throttle value
+pub const MAX_THROTTLE: u16 = 2047;
+
+/// The bit rate of the DShot protocol
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DshotSpeed {
+    /// 150 kbit/s
+    Dshot150,
+    /// 300 kbit/s
+    Dshot300,
+    /// 600 kbit/s
+    Dshot600,
+}
+
+impl DshotSpeed {
+    /// Length of a bit period in nanoseconds
+    pub const fn bit_period(&self) -> u32 {
+        match self {
+            DshotSpeed::Dshot150 => 6_667,
+            DshotSpeed::Dshot300 => 3_333,
+            DshotSpeed::Dshot600 => 1_667,
+        }
+    }
+
+    // Length of the high level of a one in nanoseconds
+    const fn one_high(&self) -> u32 {
+        self.bit_period() * 3 / 4
+    }
+
+    // Length of the high level of a zero in nanoseconds
+    const fn zero_high(&self) -> u32 {
+        self.bit_period() * 3 / 8
+    }
+}
+
+/// A DShot frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DshotFrame {
+    /// The throttle value (or command)
+    pub throttle: u16,
+    /// Request telemetry from the ESC
+    pub telemetry: bool,
+    /// Use the inverted CRC (and signal levels) of bidirectional DShot
+    pub inverted: bool,
+}
+
+impl DshotFrame {
+    /// Create a frame for the given throttle value (or command)
+    pub fn new(throttle: u16, telemetry: bool) -> Result<Self, ProtocolError> {
+        if throttle > MAX_THROTTLE {
+            return Err(ProtocolError::InvalidValue);
+        }
+
+        Ok(DshotFrame {
+            throttle,
+            telemetry,
+            inverted: false,
+        })
+    }
+
+    /// The 16 bit packet including the CRC
+    pub fn packet(&self) -> u16 {
+        let value = (self.throttle & MAX_THROTTLE) << 1 | self.telemetry as u16;
+        value << 4 | crc(value, self.inverted)
+    }
+
+    /// Create a frame from a received 16 bit packet, checking its CRC
+    pub fn from_packet(packet: u16, inverted: bool) -> Result<Self, ProtocolError> {
+        let value = packet >> 4;
+
+        if packet & 0xf != crc(value, inverted) {
+            return Err(ProtocolError::InvalidChecksum);
+        }
+
+        Ok(DshotFrame {
+            throttle: value >> 1,
+            telemetry: value & 1 != 0,
+            inverted,
+        })
+    }
+}
+
+// Calculate the CRC of the 12 bit value (throttle and telemetry bit)
+fn crc(value: u16, inverted: bool) -> u16 {
+    let crc = value ^ (value >> 4) ^ (value >> 8);
+
+    if inverted {
+        !crc & 0xf
+    } else {
+        crc & 0xf
+    }
+}
+
+/// Encode a DShot frame
+///
+/// The bits are sent MSB first and encoded in the length of the active
+/// level.
+pub fn encode(frame: DshotFrame, speed: DshotSpeed) -> [Timing; FRAME_LENGTH] {
+    let packet = frame.packet();
+
+    let mut timings = [(0, 0); FRAME_LENGTH];
+    for (bit, timing) in timings[..BITS].iter_mut().enumerate() {
+        let high = match packet & (1 << (BITS - 1 - bit)) {
+            0 => speed.zero_high(),
+            _ => speed.one_high(),
+        };
+        *timing = (high, speed.bit_period() - high);
+    }
+
+    // The last entry stays an end marker
+    timings
+}
+
+/// Decode a received DShot frame
+///
+/// A bit is taken as a one if its active level lasts longer than half of
+/// the bit period.
+pub fn decode(
+    timings: impl Iterator<Item = Timing>,
+    speed: DshotSpeed,
+    inverted: bool,
+) -> Result<DshotFrame, ProtocolError> {
+    let mut packet = 0u16;
+    let mut count = 0;
+
+    for (high, _) in timings.take(BITS) {
+        if high == 0 || high >= speed.bit_period() {
+            return Err(ProtocolError::InvalidTiming);
+        }
+
+        packet = packet << 1 | (high > speed.bit_period() / 2) as u16;
+        count += 1;
+    }
+
+    if count < BITS {
+        return Err(ProtocolError::InvalidLength);
+    }
+
+    DshotFrame::from_packet(packet, inverted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEEDS: [DshotSpeed; 3] = [
+        DshotSpeed::Dshot150,
+        DshotSpeed::Dshot300,
+        DshotSpeed::Dshot600,
+    ];
+
+    #[test]
+    fn known_packet() {
+        let frame = DshotFrame::new(1046, false).unwrap();
+        assert_eq!(frame.packet(), 0x82c6);
+
+        let inverted = DshotFrame {
+            inverted: true,
+            ..frame
+        };
+        assert_eq!(inverted.packet(), 0x82c9);
+
+        // 0x82c6 starts with the bits 1 0
+        let timings = encode(frame, DshotSpeed::Dshot600);
+        assert_eq!(timings[0], (1_250, 417));
+        assert_eq!(timings[1], (625, 1_042));
+        assert_eq!(timings[FRAME_LENGTH - 1], (0, 0));
+    }
+
+    #[test]
+    fn round_trip() {
+        for throttle in [0, 1, 47, 48, 1046, MAX_THROTTLE] {
+            for telemetry in [false, true] {
+                for inverted in [false, true] {
+                    let frame = DshotFrame {
+                        throttle,
+                        telemetry,
+                        inverted,
+                    };
+
+                    for speed in SPEEDS {
+                        let timings = encode(frame, speed);
+
+                        assert_eq!(decode(timings.into_iter(), speed, inverted), Ok(frame));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_frames() {
+        assert_eq!(
+            DshotFrame::new(MAX_THROTTLE + 1, false),
+            Err(ProtocolError::InvalidValue)
+        );
+        assert_eq!(
+            DshotFrame::from_packet(0x82c7, false),
+            Err(ProtocolError::InvalidChecksum)
+        );
+        assert_eq!(
+            DshotFrame::from_packet(0x82c6, true),
+            Err(ProtocolError::InvalidChecksum)
+        );
+
+        let frame = DshotFrame::new(1046, false).unwrap();
+        let timings = encode(frame, DshotSpeed::Dshot300);
+        assert_eq!(
+            decode(timings[..10].iter().copied(), DshotSpeed::Dshot300, false),
+            Err(ProtocolError::InvalidLength)
+        );
+        assert_eq!(
+            decode(timings.into_iter(), DshotSpeed::Dshot600, false),
+            Err(ProtocolError::InvalidTiming)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! # Hardware independent RMT protocol logic
+//!
+//! The frame, checksum and bit timing logic of the protocols supported by the
+//! RMT driver of `esp-hal-common`, and the layout of the raw pulse code
+//! entries in the RMT RAM. Nothing in here touches any hardware, so the crate
+//! builds (and is tested) on the host.
+//!
+//! * [`nec`]: NEC infrared remote protocol
+//! * [`rc5`]: Philips RC5 infrared remote protocol
+//! * [`dshot`]: DShot150/300/600 ESC protocol
+//! * [`pulse_code`]: raw pulse code entries and the conversion of pulse
+//!   lengths into clock ticks
+//!
+//! The protocols describe a frame as a sequence of [`Timing`]s, the RMT
+//! driver turns them into pulse codes starting at the active level of the
+//! protocol.
+#![no_std]
+
+pub mod dshot;
+pub mod nec;
+pub mod pulse_code;
+pub mod rc5;
+
+/// Lengths (in nanoseconds) of the active part of a pulse code and the idle
+/// part following it
+///
+/// A timing of `(0, 0)` is an end marker.
+pub type Timing = (u32, u32);
+
+/// Errors that can occur while encoding or decoding a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProtocolError {
+    /// A value does not fit into the corresponding field of the frame
+    InvalidValue,
+    /// The sequence contains too few pulse codes for a complete frame
+    InvalidLength,
+    /// A pulse length does not match the timing of the protocol
+    InvalidTiming,
+    /// The checksum (or the inverted copy of a field) does not match
+    InvalidChecksum,
+}
+
+// Relative deviation from the nominal pulse length accepted when decoding
+// (in percent)
+const TOLERANCE_PERCENT: u32 = 25;
+
+// Check whether a received pulse length matches the nominal length (in
+// nanoseconds) within the tolerance
+fn matches(length: u32, nominal: u32) -> bool {
+    let deviation = nominal * TOLERANCE_PERCENT / 100;
+
+    (nominal - deviation..=nominal + deviation).contains(&length)
+}
@@ -0,0 +1,199 @@
+//! # NEC Infrared Remote Protocol
+//!
+//! A frame starts with a 9 ms burst followed by a 4.5 ms space. The 32 data
+//! bits (address, inverted address, command, inverted command, each sent LSB
+//! first) are encoded in the length of the space following a 562.5 us
+//! burst. A final burst terminates the frame. While a button is held, repeat
+//! codes (9 ms burst, 2.25 ms space, 562.5 us burst) are sent.
+//!
+//! The extended variant of the protocol replaces the inverted address by the
+//! upper byte of a 16 bit address.
+
+use crate::{matches, ProtocolError, Timing};
+
+/// Number of pulse codes of a frame (leader, 32 data bits and stop bit)
+pub const FRAME_LENGTH: usize = 34;
+
+/// Number of pulse codes of a repeat code
+pub const REPEAT_LENGTH: usize = 2;
+
+const LEADER_MARK_NS: u32 = 9_000_000;
+const LEADER_SPACE_NS: u32 = 4_500_000;
+const REPEAT_SPACE_NS: u32 = 2_250_000;
+const BIT_MARK_NS: u32 = 562_500;
+const ZERO_SPACE_NS: u32 = 562_500;
+const ONE_SPACE_NS: u32 = 1_687_500;
+
+/// A decoded NEC frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NecCommand {
+    /// The address of the device (only the lower byte is used unless the
+    /// frame is an extended one)
+    pub address: u16,
+    /// The command
+    pub command: u8,
+    /// Whether the frame is a repeat code (address and command are 0 then)
+    pub repeat: bool,
+}
+
+/// Encode a standard NEC frame
+pub fn encode(address: u8, command: u8) -> [Timing; FRAME_LENGTH] {
+    encode_extended(address as u16 | (!address as u16) << 8, command)
+}
+
+/// Encode an extended NEC frame with a 16 bit address
+///
+/// The data bits are sent LSB first and encoded in the length of the space.
+pub fn encode_extended(address: u16, command: u8) -> [Timing; FRAME_LENGTH] {
+    let data = frame_data(address, command);
+
+    let mut timings = [(0, 0); FRAME_LENGTH];
+    timings[0] = (LEADER_MARK_NS, LEADER_SPACE_NS);
+
+    for (bit, timing) in timings[1..33].iter_mut().enumerate() {
+        let space = match data & (1 << bit) {
+            0 => ZERO_SPACE_NS,
+            _ => ONE_SPACE_NS,
+        };
+        *timing = (BIT_MARK_NS, space);
+    }
+
+    // The stop bit also acts as the end marker of the sequence
+    timings[33] = (BIT_MARK_NS, 0);
+
+    timings
+}
+
+/// Encode a repeat code
+pub fn encode_repeat() -> [Timing; REPEAT_LENGTH] {
+    [(LEADER_MARK_NS, REPEAT_SPACE_NS), (BIT_MARK_NS, 0)]
+}
+
+/// Decode a received NEC frame or repeat code
+pub fn decode(mut timings: impl Iterator<Item = Timing>) -> Result<NecCommand, ProtocolError> {
+    let (mark, space) = timings.next().ok_or(ProtocolError::InvalidLength)?;
+
+    if !matches(mark, LEADER_MARK_NS) {
+        return Err(ProtocolError::InvalidTiming);
+    }
+
+    if matches(space, REPEAT_SPACE_NS) {
+        return Ok(NecCommand {
+            address: 0,
+            command: 0,
+            repeat: true,
+        });
+    }
+
+    if !matches(space, LEADER_SPACE_NS) {
+        return Err(ProtocolError::InvalidTiming);
+    }
+
+    // The space of the last data bit is followed by the stop bit
+    let mut data = 0u32;
+    for bit in 0..32 {
+        let (mark, space) = timings.next().ok_or(ProtocolError::InvalidLength)?;
+
+        if !matches(mark, BIT_MARK_NS) {
+            return Err(ProtocolError::InvalidTiming);
+        }
+
+        if matches(space, ONE_SPACE_NS) {
+            data |= 1 << bit;
+        } else if !matches(space, ZERO_SPACE_NS) {
+            return Err(ProtocolError::InvalidTiming);
+        }
+    }
+
+    parse_data(data)
+}
+
+// The 32 data bits of a frame: the address followed by the command and the
+// inverted command
+fn frame_data(address: u16, command: u8) -> u32 {
+    address as u32 | (command as u32) << 16 | (!command as u32) << 24
+}
+
+// Split the data bits of a frame into the address and the command
+fn parse_data(data: u32) -> Result<NecCommand, ProtocolError> {
+    let command = (data >> 16) as u8;
+    if command != !(data >> 24) as u8 {
+        return Err(ProtocolError::InvalidChecksum);
+    }
+
+    // Frames with an inverted copy of the address carry an 8 bit address
+    let address = match (data as u8, (data >> 8) as u8) {
+        (low, high) if low == !high => low as u16,
+        _ => data as u16,
+    };
+
+    Ok(NecCommand {
+        address,
+        command,
+        repeat: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_frame() {
+        // Address 0x04 and command 0x08 as sent by LG remotes
+        assert_eq!(frame_data(0xfb04, 0x08), 0xf708_fb04);
+
+        let timings = encode(0x04, 0x08);
+        assert_eq!(timings[0], (LEADER_MARK_NS, LEADER_SPACE_NS));
+        assert_eq!(timings[1], (BIT_MARK_NS, ZERO_SPACE_NS));
+        assert_eq!(timings[3], (BIT_MARK_NS, ONE_SPACE_NS));
+        assert_eq!(timings[33], (BIT_MARK_NS, 0));
+    }
+
+    #[test]
+    fn round_trip() {
+        for address in [0x00, 0x04, 0x5a, 0xff] {
+            for command in [0x00, 0x08, 0xa5, 0xff] {
+                let decoded = decode(encode(address, command).into_iter()).unwrap();
+
+                assert_eq!(decoded.address, address as u16);
+                assert_eq!(decoded.command, command);
+                assert!(!decoded.repeat);
+            }
+        }
+    }
+
+    #[test]
+    fn extended_address() {
+        let decoded = decode(encode_extended(0x1234, 0x42).into_iter()).unwrap();
+
+        assert_eq!(decoded.address, 0x1234);
+        assert_eq!(decoded.command, 0x42);
+    }
+
+    #[test]
+    fn repeat_code() {
+        let decoded = decode(encode_repeat().into_iter()).unwrap();
+
+        assert!(decoded.repeat);
+    }
+
+    #[test]
+    fn invalid_frames() {
+        // The inverted command doesn't match
+        assert_eq!(parse_data(0xf808_fb04), Err(ProtocolError::InvalidChecksum));
+
+        let timings = encode(0x04, 0x08);
+        assert_eq!(
+            decode(timings[..20].iter().copied()),
+            Err(ProtocolError::InvalidLength)
+        );
+
+        let mut timings = timings;
+        timings[5].1 = 1_100_000;
+        assert_eq!(
+            decode(timings.into_iter()),
+            Err(ProtocolError::InvalidTiming)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! # Raw Pulse Codes
+//!
+//! An entry of the RMT RAM holds two pulses, each with a level and a 15 bit
+//! length in clock ticks of the channel: the first length resides in bits
+//! [14:0] and its level in bit 15, the second length in bits [30:16] and its
+//! level in bit 31. A length of zero ends a transmission.
+
+/// Longest pulse (in clock ticks) that fits into a pulse code
+pub const MAX_TICKS: u32 = 0x7fff;
+
+/// Pack the levels and lengths (in clock ticks) of two pulses into an entry
+///
+/// Lengths above [`MAX_TICKS`] are truncated.
+pub const fn pack(level1: bool, ticks1: u32, level2: bool, ticks2: u32) -> u32 {
+    (level1 as u32) << 15
+        | (ticks1 & MAX_TICKS)
+        | (level2 as u32) << 31
+        | (ticks2 & MAX_TICKS) << 16
+}
+
+/// Split an entry into the levels and lengths (in clock ticks) of its two
+/// pulses
+pub const fn unpack(entry: u32) -> (bool, u32, bool, u32) {
+    (
+        entry & (1 << 15) != 0,
+        entry & MAX_TICKS,
+        entry & (1 << 31) != 0,
+        (entry >> 16) & MAX_TICKS,
+    )
+}
+
+/// Convert a pulse length in nanoseconds into the nearest number of clock
+/// ticks of `tick_frequency` (in Hz)
+///
+/// Returns `None` if the result doesn't fit into a pulse code, or if a pulse
+/// length other than zero would be rounded to zero (and taken as an end
+/// marker).
+pub fn nanos_to_ticks(nanos: u32, tick_frequency: u32) -> Option<u32> {
+    let ticks = (nanos as u64 * tick_frequency as u64 + 500_000_000) / 1_000_000_000;
+
+    if ticks > MAX_TICKS as u64 || (ticks == 0 && nanos != 0) {
+        return None;
+    }
+
+    Some(ticks as u32)
+}
+
+/// Convert clock ticks of `tick_frequency` (in Hz) into nanoseconds
+pub fn ticks_to_nanos(ticks: u32, tick_frequency: u32) -> u32 {
+    let nanos = ticks as u64 * 1_000_000_000 / tick_frequency as u64;
+
+    nanos.min(u32::MAX as u64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_entries() {
+        assert_eq!(pack(true, 10, false, 20), 0x0014_800a);
+        assert_eq!(pack(false, MAX_TICKS, true, 1), 0x8001_7fff);
+        assert_eq!(pack(false, 0, false, 0), 0);
+
+        assert_eq!(unpack(0x0014_800a), (true, 10, false, 20));
+        assert_eq!(unpack(0x8001_7fff), (false, MAX_TICKS, true, 1));
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        for level1 in [false, true] {
+            for level2 in [false, true] {
+                for ticks in [0, 1, 1000, MAX_TICKS] {
+                    let entry = pack(level1, ticks, level2, MAX_TICKS - ticks);
+                    assert_eq!(unpack(entry), (level1, ticks, level2, MAX_TICKS - ticks));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tick_conversion() {
+        // 80 MHz, 12.5 ns per tick
+        assert_eq!(nanos_to_ticks(0, 80_000_000), Some(0));
+        assert_eq!(nanos_to_ticks(320, 80_000_000), Some(26));
+        assert_eq!(nanos_to_ticks(10, 80_000_000), Some(1));
+        assert_eq!(ticks_to_nanos(26, 80_000_000), 325);
+
+        // 1 MHz (REF_TICK)
+        assert_eq!(nanos_to_ticks(9_000_000, 1_000_000), Some(9_000));
+        assert_eq!(ticks_to_nanos(9_000, 1_000_000), 9_000_000);
+    }
+
+    #[test]
+    fn invalid_lengths() {
+        // Rounded to zero, this would end the transmission
+        assert_eq!(nanos_to_ticks(6, 80_000_000), None);
+        // Longer than 15 bits of ticks
+        assert_eq!(nanos_to_ticks(409_600, 80_000_000), None);
+        assert_eq!(nanos_to_ticks(409_587, 80_000_000), Some(MAX_TICKS));
+    }
+}
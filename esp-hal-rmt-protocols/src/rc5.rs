@@ -0,0 +1,209 @@
+//! # Philips RC5 Infrared Remote Protocol
+//!
+//! A frame consists of 14 Manchester encoded bits of 1.778 ms each, sent MSB
+//! first: two start bits (the second one carries the inverted bit 6 of the
+//! command in the extended RC5X variant), a toggle bit, 5 address bits and 6
+//! command bits. A one is sent as a space followed by a burst, a zero as a
+//! burst followed by a space.
+//!
+//! Since the line is idle before a frame, the space of the first start bit
+//! is not part of the encoded sequence.
+
+use crate::{matches, ProtocolError, Timing};
+
+/// Maximum number of pulse codes of a frame (shorter frames are padded
+/// with end markers)
+pub const FRAME_LENGTH: usize = 14;
+
+const BITS: usize = 14;
+const HALF_BIT_NS: u32 = 889_000;
+
+/// An RC5 frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rc5Command {
+    /// The address of the device (5 bits)
+    pub address: u8,
+    /// The command (7 bits, commands above 63 are RC5X commands)
+    pub command: u8,
+    /// The toggle bit, which changes every time a button is pressed again
+    pub toggle: bool,
+}
+
+/// Encode an RC5 frame
+pub fn encode(frame: Rc5Command) -> Result<[Timing; FRAME_LENGTH], ProtocolError> {
+    let data = frame_data(&frame)?;
+
+    // Collect the lengths (in half bits) of the alternating bursts and spaces,
+    // starting with the burst of the first start bit
+    let mut runs = [0u32; 2 * BITS];
+    let mut count = 0;
+    let mut level = true;
+
+    for bit in (0..BITS).rev() {
+        let one = data & (1 << bit) != 0;
+
+        // A one is a space followed by a burst, a zero the other way around
+        for half in [!one, one] {
+            if bit == BITS - 1 && !half {
+                // Skip the leading space of the first start bit
+                continue;
+            }
+
+            if half != level {
+                level = half;
+                count += 1;
+            }
+            runs[count] += 1;
+        }
+    }
+
+    // A trailing space is not needed, the line stays idle after the frame
+    if !level {
+        count -= 1;
+    }
+
+    let mut timings = [(0, 0); FRAME_LENGTH];
+    for (timing, pair) in timings.iter_mut().zip(runs[..=count].chunks(2)) {
+        let space = pair.get(1).copied().unwrap_or(0);
+        *timing = (pair[0] * HALF_BIT_NS, space * HALF_BIT_NS);
+    }
+
+    Ok(timings)
+}
+
+/// Decode a received RC5 frame
+pub fn decode(timings: impl Iterator<Item = Timing>) -> Result<Rc5Command, ProtocolError> {
+    // Expand the pulse lengths into half bits, the space of the first start
+    // bit is not part of the received sequence
+    let mut halves = [false; 2 * BITS];
+    let mut count = 1;
+
+    'timings: for (mark, space) in timings {
+        for (level, length) in [(true, mark), (false, space)] {
+            // A length of zero marks the end of the sequence
+            if length == 0 {
+                break 'timings;
+            }
+
+            let run = if matches(length, HALF_BIT_NS) {
+                1
+            } else if matches(length, 2 * HALF_BIT_NS) {
+                2
+            } else {
+                return Err(ProtocolError::InvalidTiming);
+            };
+
+            for _ in 0..run {
+                *halves.get_mut(count).ok_or(ProtocolError::InvalidLength)? = level;
+                count += 1;
+            }
+        }
+    }
+
+    // The trailing space of a frame ending with a zero is not received
+    if count == 2 * BITS - 1 {
+        count += 1;
+    }
+
+    if count != 2 * BITS {
+        return Err(ProtocolError::InvalidLength);
+    }
+
+    let mut data = 0u16;
+    for pair in halves.chunks(2) {
+        let bit = match (pair[0], pair[1]) {
+            (false, true) => 1,
+            (true, false) => 0,
+            _ => return Err(ProtocolError::InvalidTiming),
+        };
+        data = data << 1 | bit;
+    }
+
+    Ok(parse_data(data))
+}
+
+// The 14 bits of a frame, the second start bit carries the inverted bit 6 of
+// the command
+fn frame_data(frame: &Rc5Command) -> Result<u16, ProtocolError> {
+    if frame.address > 0x1f || frame.command > 0x7f {
+        return Err(ProtocolError::InvalidValue);
+    }
+
+    Ok(1 << 13
+        | ((frame.command & 0x40 == 0) as u16) << 12
+        | (frame.toggle as u16) << 11
+        | (frame.address as u16) << 6
+        | (frame.command & 0x3f) as u16)
+}
+
+fn parse_data(data: u16) -> Rc5Command {
+    Rc5Command {
+        address: (data >> 6) as u8 & 0x1f,
+        command: (data & 0x3f) as u8 | ((data & (1 << 12) == 0) as u8) << 6,
+        toggle: data & (1 << 11) != 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Rc5Command = Rc5Command {
+        address: 5,
+        command: 35,
+        toggle: false,
+    };
+
+    #[test]
+    fn known_frame() {
+        // Start bits 11, toggle bit 0, address 00101 and command 100011
+        assert_eq!(frame_data(&FRAME), Ok(0x3163));
+
+        // Start bits, toggle bit and the first address bits: 1 1 0 0 0 1
+        let timings = encode(FRAME).unwrap();
+        assert_eq!(timings[0], (HALF_BIT_NS, HALF_BIT_NS));
+        assert_eq!(timings[1], (2 * HALF_BIT_NS, HALF_BIT_NS));
+        assert_eq!(timings[2], (HALF_BIT_NS, HALF_BIT_NS));
+        assert_eq!(timings[3], (HALF_BIT_NS, 2 * HALF_BIT_NS));
+    }
+
+    #[test]
+    fn round_trip() {
+        for address in 0..=0x1f {
+            for command in 0..=0x7f {
+                for toggle in [false, true] {
+                    let frame = Rc5Command {
+                        address,
+                        command,
+                        toggle,
+                    };
+                    let timings = encode(frame).unwrap();
+
+                    assert_eq!(decode(timings.into_iter()), Ok(frame));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_frames() {
+        let frame = Rc5Command {
+            address: 0x20,
+            ..FRAME
+        };
+        assert_eq!(encode(frame), Err(ProtocolError::InvalidValue));
+
+        let timings = encode(FRAME).unwrap();
+        assert_eq!(
+            decode(timings[..3].iter().copied()),
+            Err(ProtocolError::InvalidLength)
+        );
+
+        let mut timings = timings;
+        timings[1].0 = 3 * HALF_BIT_NS;
+        assert_eq!(
+            decode(timings.into_iter()),
+            Err(ProtocolError::InvalidTiming)
+        );
+    }
+}
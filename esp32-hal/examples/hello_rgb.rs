@@ -50,7 +50,12 @@ fn main() -> ! {
     rtc.rwdt.disable();
 
     // Configure RMT peripheral globally
-    let pulse = PulseControl::new(peripherals.RMT, &mut system.peripheral_clock_control).unwrap();
+    let pulse = PulseControl::new(
+        peripherals.RMT,
+        &mut system.peripheral_clock_control,
+        &clocks,
+    )
+    .unwrap();
 
     // We use one of the RMT channels to instantiate a `SmartLedsAdapter` which can
     // be used directly with all `smart_led` implementations
@@ -4,6 +4,7 @@ pub use embedded_hal as ehal;
 pub use esp_hal_common::{
     clock,
     cpu_control::CpuControl,
+    dma,
     efuse,
     gpio as gpio_types,
     i2c,
@@ -57,6 +57,7 @@ fn main() -> ! {
         0,
         0,
         0,
+        &clocks,
     )
     .unwrap();
 
@@ -48,6 +48,7 @@ fn main() -> ! {
         0,
         0,
         0,
+        &clocks,
     )
     .unwrap();
 
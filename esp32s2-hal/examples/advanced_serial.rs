@@ -44,6 +44,7 @@ fn main() -> ! {
         data_bits: DataBits::DataBits8,
         parity: Parity::ParityNone,
         stop_bits: StopBits::STOP1,
+        ..Config::default()
     };
 
     let io = IO::new(peripherals.GPIO, peripherals.IO_MUX);
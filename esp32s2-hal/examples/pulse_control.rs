@@ -10,7 +10,7 @@ use esp32s2_hal::{
     gpio::IO,
     pac::Peripherals,
     prelude::*,
-    pulse_control::{ConfiguredChannel, OutputChannel, PulseCode, RepeatMode},
+    pulse_control::{ClockSource, ConfiguredChannel, OutputChannel, PulseCode, RepeatMode},
     timer::TimerGroup,
     PulseControl,
     Rtc,
@@ -35,7 +35,12 @@ fn main() -> ! {
     let io = IO::new(peripherals.GPIO, peripherals.IO_MUX);
 
     // Configure RMT peripheral globally
-    let pulse = PulseControl::new(peripherals.RMT, &mut system.peripheral_clock_control).unwrap();
+    let pulse = PulseControl::new(
+        peripherals.RMT,
+        &mut system.peripheral_clock_control,
+        &clocks,
+    )
+    .unwrap();
 
     let mut rmt_channel0 = pulse.channel0;
 
@@ -44,7 +49,8 @@ fn main() -> ! {
         .set_idle_output_level(false)
         .set_carrier_modulation(false)
         .set_channel_divider(1)
-        .set_idle_output(true);
+        .set_idle_output(true)
+        .set_clock_source(ClockSource::APB);
 
     // Assign GPIO pin where pulses should be sent to
     let mut rmt_channel0 = rmt_channel0.assign_pin(io.pins.gpio4);
@@ -56,6 +56,7 @@ fn main() -> ! {
         0,
         0,
         0,
+        &clocks,
     )
     .unwrap();
 
@@ -42,6 +42,7 @@ fn main() -> ! {
         0,
         0,
         0,
+        &clocks,
     )
     .unwrap();
 